@@ -0,0 +1,167 @@
+//! Generates the solution registry of the `all-days` binary, see `src/template/registry.rs`,
+//! and a test per example case of every solution, see `src/template/example_cases.rs`.
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+    println!("cargo:rerun-if-changed=src/bin");
+
+    // tags entries of the benchmark history, see `src/template/history.rs`.
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    if let Ok(output) = Command::new(rustc).arg("--version").output()
+        && output.status.success()
+    {
+        let version = String::from_utf8_lossy(&output.stdout);
+        println!("cargo:rustc-env=AOC_RUSTC_VERSION={}", version.trim());
+    }
+
+    let mut solutions: Vec<(String, String)> = vec![];
+
+    if let Ok(entries) = fs::read_dir(&bin_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if !is_solution_name(name) || path.extension().is_none_or(|ext| ext != "rs") {
+                continue;
+            }
+            let is_solution = fs::read_to_string(&path).is_ok_and(|s| s.contains("solution!("));
+            if is_solution {
+                solutions.push((name.to_string(), path.display().to_string()));
+            }
+        }
+    }
+
+    solutions.sort();
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let examples_dir = Path::new(&out_dir).join("examples");
+    fs::create_dir_all(&examples_dir).unwrap();
+
+    let mut watched: Vec<PathBuf> = vec![];
+    for (name, _) in &solutions {
+        let (year, day) = name.split_at(4);
+        let data_dir = Path::new(&manifest_dir)
+            .join("data")
+            .join(year)
+            .join("examples");
+        if data_dir.exists() && !watched.contains(&data_dir) {
+            println!("cargo:rerun-if-changed={}", data_dir.display());
+            watched.push(data_dir.clone());
+        }
+        let manifest = fs::read_to_string(data_dir.join(format!("{}.json", &day[1..]))).ok();
+        let tests = example_tests(manifest.as_deref());
+        fs::write(examples_dir.join(format!("{name}.rs")), tests).unwrap();
+    }
+
+    // NOTE: the solutions in `all-days` include the tests of their binary under its name, the tests only run per day.
+    fs::write(examples_dir.join("all-days.rs"), "").unwrap();
+
+    // NOTE: dhat installs a global allocator per solution, which can not be linked into a single binary.
+    if env::var_os("CARGO_FEATURE_DHAT_HEAP").is_some() {
+        solutions.clear();
+    }
+
+    let solutions: Vec<(String, String)> = solutions
+        .into_iter()
+        .map(|(name, path)| (name.replace('-', "_"), path))
+        .collect();
+
+    let mut registry = String::new();
+
+    for (module, path) in &solutions {
+        registry.push_str(&format!(
+            "#[allow(dead_code)]\n#[path = {path:?}]\nmod _{module};\n\n"
+        ));
+    }
+
+    registry
+        .push_str("pub static SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n");
+    for (module, _) in &solutions {
+        registry.push_str(&format!(
+            "    advent_of_code::template::registry::Solution {{ puzzle: _{module}::PUZZLE, run: _{module}::__run }},\n"
+        ));
+    }
+    registry.push_str("];\n");
+
+    fs::write(Path::new(&out_dir).join("solutions.rs"), registry).unwrap();
+}
+
+/// Generates a test module with a test per case of an example manifest.
+/// A manifest that can't be read becomes a single failing test, which reports the error.
+fn example_tests(manifest: Option<&str>) -> String {
+    let Some(manifest) = manifest else {
+        return String::new();
+    };
+
+    let names: Option<Vec<String>> = JsonValue::from_str(manifest).ok().and_then(|json| {
+        json.get::<Vec<JsonValue>>()?
+            .iter()
+            .map(|case| {
+                case.get::<HashMap<String, JsonValue>>()?
+                    .get("name")?
+                    .get::<String>()
+                    .cloned()
+            })
+            .collect()
+    });
+
+    let mut tests = String::from("mod examples {\n");
+
+    let Some(names) = names else {
+        tests.push_str(
+            "    #[test]\n    fn manifest() {\n        advent_of_code::template::example_cases::read(super::PUZZLE).unwrap();\n    }\n}\n",
+        );
+        return tests;
+    };
+
+    let mut idents: Vec<String> = vec![];
+    for name in names {
+        let mut ident = to_ident(&name);
+        if idents.contains(&ident) {
+            ident = format!("{ident}_{}", idents.len() + 1);
+        }
+        tests.push_str(&format!(
+            "    #[test]\n    fn {ident}() {{\n        advent_of_code::template::example_cases::test_case(super::PUZZLE, {name:?}, super::__run);\n    }}\n\n"
+        ));
+        idents.push(ident);
+    }
+
+    tests.push_str("}\n");
+    tests
+}
+
+/// Turns the name of an example case into the name of its test, e.g. `small grid` into `case_small_grid`.
+fn to_ident(name: &str) -> String {
+    let ident: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("case_{ident}")
+}
+
+/// Matches names of solution binaries, e.g. `2025-01`.
+fn is_solution_name(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    bytes.len() == 7
+        && bytes[4] == b'-'
+        && bytes
+            .iter()
+            .enumerate()
+            .all(|(i, b)| i == 4 || b.is_ascii_digit())
+}
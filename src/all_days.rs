@@ -0,0 +1,70 @@
+//! Runs `cargo all` and `cargo time` with every solution compiled into this binary.
+use advent_of_code::template::commands::{all, time};
+use advent_of_code::template::{Day, PuzzleId, Year, registry};
+use std::{env, process};
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+fn parse() -> Result<(), Box<dyn std::error::Error>> {
+    let mut raw_args: Vec<_> = env::args_os().skip(1).collect();
+    let compare = time::take_compare_arg(&mut raw_args);
+    let mut args = pico_args::Arguments::from_vec(raw_args);
+
+    let subcommand = args.subcommand()?;
+
+    let Some(year) = args.opt_value_from_str("--year")?.or_else(Year::from_env) else {
+        eprintln!("No year specified. Pass `--year <year>` or set AOC_YEAR in .cargo/config.toml.");
+        process::exit(1);
+    };
+
+    registry::install(SOLUTIONS);
+
+    match subcommand.as_deref() {
+        Some("all") => {
+            let all_args = all::parse_args(&mut args)?;
+            finish(args);
+            all::handle(year, &all_args);
+        }
+        Some("time") => {
+            let time_args = time::parse_args(year, &mut args, compare)?;
+            finish(args);
+            time::handle(year, time_args);
+        }
+        // runs a single day, used by parallel runs of `all` and `time`.
+        Some("day") => {
+            let day: Day = args.free_from_str()?;
+            // NOTE: `--time` is read by the runner.
+            let _ = args.contains("--time");
+            finish(args);
+
+            if !registry::run_day(PuzzleId::new(year, day)) {
+                eprintln!("No solution for day {day} of {year}.");
+                process::exit(1);
+            }
+        }
+        Some(x) => {
+            eprintln!("Unknown command: {x}. This binary only runs `all` and `time`.");
+            process::exit(1);
+        }
+        None => {
+            eprintln!("No command specified.");
+            process::exit(1);
+        }
+    }
+
+    Ok(())
+}
+
+fn finish(args: pico_args::Arguments) {
+    let remaining = args.finish();
+    if !remaining.is_empty() {
+        eprintln!("Warning: unknown argument(s): {remaining:?}.");
+    }
+}
+
+fn main() {
+    if let Err(err) = parse() {
+        eprintln!("Error: {err}");
+        process::exit(1);
+    }
+}
@@ -1,8 +1,19 @@
 use std::collections::{HashMap, HashSet};
 
-advent_of_code::solution!(8);
+use advent_of_code::template::Params;
 
-pub fn part_one(input: &str) -> Option<u64> {
+advent_of_code::solution!(8, params = PARAMS);
+
+const PARAMS: Params<Config> = Params {
+    example: Config { connections: 10 },
+    real: Config { connections: 1000 },
+};
+
+pub struct Config {
+    connections: usize,
+}
+
+pub fn part_one(input: &str, config: &Config) -> Option<u64> {
     let boxes = parse_input(input);
 
     let mut distances: Vec<((Point, Point), u64)> = Vec::with_capacity(boxes.len()*boxes.len());
@@ -19,10 +30,9 @@ pub fn part_one(input: &str) -> Option<u64> {
 
     let mut circuits: Vec<HashSet<Point>> = Vec::new();
     let mut box_to_circuits: HashMap<Point, usize> = HashMap::new();
-    for ((p1, p2), _) in distances[..1000].iter() {
+    for ((p1, p2), _) in distances[..config.connections].iter() {
         let i1 = box_to_circuits.get(p1).copied();
         let i2 = box_to_circuits.get(p2).copied();
-        dbg!(p1, p2, i1, i2, &circuits.len());
         match (i1, i2) {
             (None, None) => {
                 let i = circuits.len();
@@ -57,7 +67,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(circuits.iter().rev().take(3).fold(1, |acc, c| acc * c.len() as u64))
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(input: &str, _config: &Config) -> Option<u64> {
     let boxes = parse_input(input);
     let num_boxes = boxes.len();
 
@@ -164,13 +174,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", PUZZLE),
+            &PARAMS.example,
+        );
         assert_eq!(result, Some(40));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", PUZZLE),
+            &PARAMS.example,
+        );
         assert_eq!(result, None);
     }
 }
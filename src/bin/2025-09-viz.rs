@@ -29,7 +29,7 @@ fn parse(input: &str) -> IResult<&str, Vec<IVec2>> {
 struct Data(Vec<IVec2>);
 
 fn startup(mut commands: Commands) {
-    let input = include_str!("../../data/inputs/09.txt");
+    let input = include_str!("../../data/2025/inputs/09.txt");
     let (_, vecs) = parse(input).unwrap();
     commands.insert_resource(Data(vecs));
     commands.spawn((
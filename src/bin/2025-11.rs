@@ -106,20 +106,6 @@ fn id_to_name(id: usize) -> String {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(2));
-    }
-
     #[test]
     fn test_encoding() {
         assert_eq!(12 / 14, 0);
@@ -1,14 +1,16 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, read, scaffold, solve, stars, time};
+use advent_of_code::template::input::Source;
+use advent_of_code::template::{Calendar, Day, PuzzleId, Year};
 use args::{AppArguments, parse};
 
-#[cfg(feature = "today")]
-use advent_of_code::template::Day;
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::commands::{all, solve, time};
+    use advent_of_code::template::input::Source;
+    use advent_of_code::template::{Day, Year};
+    use std::{env, process};
 
     pub enum AppArguments {
         Download {
@@ -21,42 +23,46 @@ mod args {
             day: Day,
             download: bool,
             overwrite: bool,
+            params: bool,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            accept: bool,
+            examples: bool,
+            watch: bool,
+            source: Source,
         },
-        All {
-            release: bool,
-        },
-        Time {
-            all: bool,
-            day: Option<Day>,
-            store: bool,
+        Stars {
+            readme: bool,
         },
+        All(all::Args),
+        Time(time::Args),
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+    /// Parses the command and the year it applies to, which defaults to `AOC_YEAR`.
+    pub fn parse() -> Result<(Year, AppArguments), Box<dyn std::error::Error>> {
+        let mut raw_args: Vec<_> = env::args_os().skip(1).collect();
+        let compare = time::take_compare_arg(&mut raw_args);
+        let example = solve::take_example_arg(&mut raw_args);
+        let mut args = pico_args::Arguments::from_vec(raw_args);
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
-            Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
+        let subcommand = args.subcommand()?;
 
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
-                }
-            }
+        let Some(year) = args.opt_value_from_str("--year")?.or_else(Year::from_env) else {
+            eprintln!(
+                "No year specified. Pass `--year <year>` or set AOC_YEAR in .cargo/config.toml."
+            );
+            process::exit(1);
+        };
+
+        let app_args = match subcommand.as_deref() {
+            Some("all") => AppArguments::All(all::parse_args(&mut args)?),
+            Some("time") => AppArguments::Time(time::parse_args(year, &mut args, compare)?),
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
@@ -67,12 +73,29 @@ mod args {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                params: args.contains("--params"),
             },
             Some("solve") => AppArguments::Solve {
+                source: match (args.opt_value_from_str::<_, String>("--input")?, example) {
+                    (Some(_), Some(_)) => {
+                        eprintln!("Pass either `--input` or `--example`, not both.");
+                        process::exit(1);
+                    }
+                    (Some(path), None) if path == "-" => Source::Stdin,
+                    (Some(path), None) => Source::File(path.into()),
+                    (None, Some(example)) => example,
+                    (None, None) => Source::Puzzle,
+                },
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                accept: args.contains("--accept"),
+                examples: args.contains("--examples"),
+                watch: args.contains("--watch"),
+            },
+            Some("stars") => AppArguments::Stars {
+                readme: args.contains("--readme"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -91,29 +114,44 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        Ok((year, app_args))
     }
 }
 
+/// Builds the puzzle id for `day`, exiting if the event of `year` does not have that day.
+fn get_puzzle(year: Year, day: Day) -> PuzzleId {
+    let last_day = Calendar::new(year).last_day();
+    if day > last_day {
+        eprintln!(
+            "Error: the {year} event only has {} puzzles.",
+            last_day.into_inner()
+        );
+        std::process::exit(1);
+    }
+    PuzzleId::new(year, day)
+}
+
 fn main() {
     match parse() {
         Err(err) => {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+        Ok((year, args)) => match args {
+            AppArguments::All(args) => all::handle(year, &args),
+            AppArguments::Time(args) => time::handle(year, args),
+            AppArguments::Download { day } => download::handle(get_puzzle(year, day)),
+            AppArguments::Read { day } => read::handle(get_puzzle(year, day)),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                params,
             } => {
-                scaffold::handle(day, overwrite);
+                let puzzle = get_puzzle(year, day);
+                scaffold::handle(puzzle, overwrite, params);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
             }
             AppArguments::Solve {
@@ -121,19 +159,43 @@ fn main() {
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                accept,
+                examples,
+                watch,
+                source,
+            } => {
+                let puzzle = get_puzzle(year, day);
+                if (submit.is_some() || accept) && (source != Source::Puzzle || examples) {
+                    eprintln!(
+                        "`--submit` and `--accept` only apply to the puzzle input, they can not be combined with `--input`, `--example` or `--examples`."
+                    );
+                    std::process::exit(1);
+                }
+
+                if !watch {
+                    solve::handle(puzzle, release, dhat, submit, accept, examples, &source);
+                } else if submit.is_some() || accept || source == Source::Stdin {
+                    eprintln!(
+                        "`--watch` can not be combined with `--submit`, `--accept` or `--input -`."
+                    );
+                    std::process::exit(1);
+                } else {
+                    solve::watch(puzzle, release, dhat, examples, &source);
+                }
+            }
+            AppArguments::Stars { readme } => stars::handle(year, readme),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                match PuzzleId::today() {
+                    Some(puzzle) => {
+                        scaffold::handle(puzzle, false, false);
+                        download::handle(puzzle);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run on days of december that \
+                            have a puzzle. Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
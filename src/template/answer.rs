@@ -0,0 +1,243 @@
+/// Typed answers of solution parts.
+///
+/// A part can return any type that implements [`ToAnswer`]: integers, strings, or a picture of
+/// letters drawn with `#`, like the answers of the puzzles that draw on a screen. Pictures drawn
+/// in the standard letter font of Advent of Code are read back into text, so they can be
+/// submitted and compared with the answer registry.
+use std::fmt::Display;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    /// Multi-line picture of letters, e.g. `.##.\n#..#\n...`.
+    Glyphs(String),
+}
+
+impl Answer {
+    /// Creates an answer from text, which is treated as a picture if it spans multiple lines.
+    pub fn from_text(text: &str) -> Self {
+        if text.trim().contains('\n') {
+            Answer::Glyphs(text.to_string())
+        } else {
+            Answer::Text(text.to_string())
+        }
+    }
+
+    /// Text of the letters in a picture, `None` for other answers or pictures that can't be read.
+    pub fn letters(&self) -> Option<String> {
+        match self {
+            Answer::Glyphs(picture) => ocr(picture),
+            _ => None,
+        }
+    }
+
+    /// Value that is submitted and stored in the answer registry.
+    /// Pictures are read into their letters; unreadable pictures are kept as they are.
+    pub fn normalized(&self) -> String {
+        match self {
+            Answer::Number(n) => n.to_string(),
+            Answer::Text(text) => text.trim().to_string(),
+            Answer::Glyphs(picture) => ocr(picture).unwrap_or_else(|| picture.trim().to_string()),
+        }
+    }
+}
+
+/// Normalizes a stored or submitted answer, see [`Answer::normalized`].
+pub fn normalize(answer: &str) -> String {
+    Answer::from_text(answer).normalized()
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(text) | Answer::Glyphs(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+/// Conversion of a part's result into an [`Answer`].
+pub trait ToAnswer {
+    fn to_answer(&self) -> Answer;
+}
+
+impl ToAnswer for Answer {
+    fn to_answer(&self) -> Answer {
+        self.clone()
+    }
+}
+
+impl ToAnswer for str {
+    fn to_answer(&self) -> Answer {
+        Answer::from_text(self)
+    }
+}
+
+impl ToAnswer for String {
+    fn to_answer(&self) -> Answer {
+        Answer::from_text(self)
+    }
+}
+
+impl ToAnswer for char {
+    fn to_answer(&self) -> Answer {
+        Answer::Text(self.to_string())
+    }
+}
+
+impl<T: ToAnswer + ?Sized> ToAnswer for &T {
+    fn to_answer(&self) -> Answer {
+        (**self).to_answer()
+    }
+}
+
+macro_rules! impl_to_answer {
+    ($($t:ty),*) => {
+        $(
+            impl ToAnswer for $t {
+                fn to_answer(&self) -> Answer {
+                    Answer::Number(i128::from(*self))
+                }
+            }
+        )*
+    };
+}
+
+impl_to_answer!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl ToAnswer for usize {
+    fn to_answer(&self) -> Answer {
+        Answer::Number(*self as i128)
+    }
+}
+
+impl ToAnswer for isize {
+    fn to_answer(&self) -> Answer {
+        Answer::Number(*self as i128)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Letters of the font, six rows high and up to five columns wide.
+const FONT: [(char, &str); 18] = [
+    ('A', ".##.|#..#|#..#|####|#..#|#..#"),
+    ('B', "###.|#..#|###.|#..#|#..#|###."),
+    ('C', ".##.|#..#|#...|#...|#..#|.##."),
+    ('E', "####|#...|###.|#...|#...|####"),
+    ('F', "####|#...|###.|#...|#...|#..."),
+    ('G', ".##.|#..#|#...|#.##|#..#|.###"),
+    ('H', "#..#|#..#|####|#..#|#..#|#..#"),
+    ('I', "###|.#.|.#.|.#.|.#.|###"),
+    ('J', "..##|...#|...#|...#|#..#|.##."),
+    ('K', "#..#|#.#.|##..|#.#.|#.#.|#..#"),
+    ('L', "#...|#...|#...|#...|#...|####"),
+    ('O', ".##.|#..#|#..#|#..#|#..#|.##."),
+    ('P', "###.|#..#|#..#|###.|#...|#..."),
+    ('R', "###.|#..#|#..#|###.|#.#.|#..#"),
+    ('S', ".###|#...|#...|.##.|...#|###."),
+    ('U', "#..#|#..#|#..#|#..#|#..#|.##."),
+    ('Y', "#...#|#...#|.#.#.|..#..|..#..|..#.."),
+    ('Z', "####|...#|..#.|.#..|#...|####"),
+];
+
+const FONT_HEIGHT: usize = 6;
+
+fn is_lit(c: char) -> bool {
+    matches!(c, '#' | '█')
+}
+
+/// Reads the letters of a picture. Letters are separated by at least one empty column.
+fn ocr(picture: &str) -> Option<String> {
+    let rows: Vec<Vec<bool>> = picture
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect())
+        .skip_while(|row: &Vec<bool>| !row.contains(&true))
+        .collect();
+    let height = rows.iter().rposition(|row| row.contains(&true))? + 1;
+    if height != FONT_HEIGHT {
+        return None;
+    }
+
+    let rows = &rows[..height];
+    let width = rows.iter().map(Vec::len).max()?;
+    let is_column_lit = |x: usize| rows.iter().any(|row| row.get(x) == Some(&true));
+
+    let mut letters = String::new();
+    let mut x = 0;
+    while x < width {
+        if !is_column_lit(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && is_column_lit(x) {
+            x += 1;
+        }
+
+        let glyph = rows
+            .iter()
+            .map(|row| {
+                (start..x)
+                    .map(|i| if row.get(i) == Some(&true) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("|");
+
+        let (letter, _) = FONT.iter().find(|(_, pattern)| *pattern == glyph)?;
+        letters.push(*letter);
+    }
+
+    (!letters.is_empty()).then_some(letters)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, ToAnswer, normalize};
+
+    const PICTURE: &str = "
+#..#.####.###..
+#..#.#....#..#.
+####.###..###..
+#..#.#....#..#.
+#..#.#....#..#.
+#..#.####.###..";
+
+    #[test]
+    fn converts_values() {
+        assert_eq!(42u64.to_answer(), Answer::Number(42));
+        assert_eq!((-7i32).to_answer(), Answer::Number(-7));
+        assert_eq!("abc".to_answer(), Answer::Text("abc".into()));
+        assert_eq!(
+            PICTURE.to_string().to_answer(),
+            Answer::Glyphs(PICTURE.into())
+        );
+    }
+
+    #[test]
+    fn reads_letters() {
+        assert_eq!(PICTURE.to_answer().letters(), Some("HEB".into()));
+        assert_eq!(PICTURE.to_answer().normalized(), "HEB");
+
+        let blocks = PICTURE.replace('#', "█").replace('.', " ");
+        assert_eq!(blocks.to_answer().normalized(), "HEB");
+    }
+
+    #[test]
+    fn keeps_unreadable_pictures() {
+        let picture = "#.#\n.#.\n#.#";
+        assert_eq!(picture.to_answer().letters(), None);
+        assert_eq!(picture.to_answer().normalized(), picture);
+    }
+
+    #[test]
+    fn normalizes_answers() {
+        assert_eq!(normalize(" 42\n"), "42");
+        assert_eq!(normalize(PICTURE), "HEB");
+    }
+}
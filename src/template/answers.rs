@@ -0,0 +1,231 @@
+/// Registry of accepted answers, used to catch regressions when refactoring solved days.
+/// Answers for a day are stored in `data/{year}/answers/{day}.json`.
+use std::{collections::HashMap, fs, io, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{PuzzleId, answer::normalize};
+
+/// Result of comparing an answer against the registry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    /// The answer matches the accepted answer.
+    Pass,
+    /// The answer differs from the accepted answer.
+    Fail { expected: String },
+    /// No answer has been accepted for this part yet.
+    Unknown,
+    /// The answer registry of the day could not be read, so the answer could not be checked.
+    Unreadable,
+}
+
+impl Check {
+    /// Short marker printed next to a part's result.
+    pub fn marker(&self) -> String {
+        match self {
+            Check::Pass => " ✔".into(),
+            Check::Fail { expected } if expected.contains('\n') => " ✘ (expected ▼)".into(),
+            Check::Fail { expected } => format!(" ✘ (expected {expected})"),
+            Check::Unknown => String::new(),
+            Check::Unreadable => " ✘ (unreadable answer registry)".into(),
+        }
+    }
+
+    /// Whether the check failed, including a registry that could not be read.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Check::Fail { .. } | Check::Unreadable)
+    }
+}
+
+/// Accepted answers for a single day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    /// Reads the accepted answers for `puzzle`. If not present, returns empty answers.
+    /// A file that can't be read or parsed is an error, so that checks fail instead of passing silently.
+    pub fn read(puzzle: PuzzleId) -> Result<Self, String> {
+        let path = get_answers_path(puzzle);
+        Answers::from_file(fs::read_to_string(&path))
+            .map_err(|e| format!("could not read \"{}\": {e}", path.display()))
+    }
+
+    fn from_file(contents: io::Result<String>) -> Result<Self, String> {
+        match contents {
+            Ok(contents) => Answers::try_from(contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Writes the accepted answers for `puzzle`, creating `data/{year}/answers` if needed.
+    pub fn store(&self, puzzle: PuzzleId) -> Result<(), io::Error> {
+        let path = get_answers_path(puzzle);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: String) {
+        match part {
+            1 => self.part_1 = Some(answer),
+            2 => self.part_2 = Some(answer),
+            _ => {}
+        }
+    }
+
+    /// Compares `answer` with the accepted answer for `part`.
+    /// Both answers are normalized first, so a picture matches the letters it shows.
+    /// A missing answer fails the check if an answer has been accepted before.
+    pub fn check(&self, part: u8, answer: Option<&str>) -> Check {
+        match (self.get(part), answer) {
+            (None, _) => Check::Unknown,
+            (Some(expected), Some(answer)) if normalize(expected) == normalize(answer) => {
+                Check::Pass
+            }
+            (Some(expected), _) => Check::Fail {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+fn get_answers_path(puzzle: PuzzleId) -> PathBuf {
+    puzzle.data_path("answers", "json")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match answer {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let answer = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("expected answers.{key} to be null or string.")),
+            _ => Ok(None),
+        };
+
+        Ok(Answers {
+            part_1: answer("part_1")?,
+            part_2: answer("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Check};
+    use std::io;
+    use tinyjson::JsonValue;
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            part_1: Some("42".into()),
+            part_2: None,
+        }
+    }
+
+    #[test]
+    fn passes_matching_answers() {
+        assert_eq!(get_mock_answers().check(1, Some("42")), Check::Pass);
+    }
+
+    #[test]
+    fn fails_mismatching_answers() {
+        assert_eq!(
+            get_mock_answers().check(1, Some("43")),
+            Check::Fail {
+                expected: "42".into()
+            }
+        );
+    }
+
+    #[test]
+    fn fails_missing_answers() {
+        assert_eq!(
+            get_mock_answers().check(1, None),
+            Check::Fail {
+                expected: "42".into()
+            }
+        );
+    }
+
+    #[test]
+    fn passes_pictures_of_accepted_letters() {
+        let answers = Answers {
+            part_1: Some("#..#\n#..#\n####\n#..#\n#..#\n#..#".into()),
+            part_2: None,
+        };
+        assert_eq!(answers.check(1, Some("H")), Check::Pass);
+    }
+
+    #[test]
+    fn handles_unknown_answers() {
+        assert_eq!(get_mock_answers().check(2, Some("1")), Check::Unknown);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let answers = get_mock_answers();
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn errors_for_unreadable_files() {
+        let missing = io::Error::from(io::ErrorKind::NotFound);
+        assert_eq!(Answers::from_file(Err(missing)), Ok(Answers::default()));
+        assert!(Answers::from_file(Ok("{\"part_1\": 42}".into())).is_err());
+        assert!(Answers::from_file(Ok("not json".into())).is_err());
+    }
+
+    #[test]
+    fn handles_partial_json() {
+        let answers = Answers::try_from(r#"{ "part_2": "abc" }"#.to_string()).unwrap();
+        assert_eq!(answers.part_1, None);
+        assert_eq!(answers.part_2, Some("abc".into()));
+    }
+}
@@ -0,0 +1,230 @@
+/// On-disk cache for responses of the puzzle website.
+///
+/// Entries are keyed by year, day and resource and stored as `{root}/{year}/{day}/{resource}`,
+/// next to a `{resource}.meta.json` file that holds the `ETag`, `Last-Modified` and fetch timestamp.
+/// Resources that do not belong to a day (e.g. the calendar) live in `{root}/{year}/{resource}`.
+/// Inputs, puzzle pages and the calendar differ per account, so the client keeps a cache per session, see [`Cache::for_session`].
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year, hash::fnv1a};
+
+pub static CACHE_DIR: &str = "data/cache";
+
+/// Metadata stored next to every cached resource.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Meta {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    /// Seconds since the unix epoch.
+    pub fetched_at: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub body: String,
+    pub meta: Meta,
+}
+
+pub struct Cache {
+    root: PathBuf,
+}
+
+impl Cache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Cache for the responses to `session`, in `{root}/{hash}` with a hash of the session cookie.
+    pub fn for_session(&self, session: &str) -> Self {
+        Self::new(
+            self.root
+                .join(format!("{:016x}", fnv1a(session.trim().as_bytes()))),
+        )
+    }
+
+    /// Returns the cached resource, if present.
+    pub fn get(&self, year: Year, day: Option<Day>, resource: &str) -> Option<Entry> {
+        let body = fs::read_to_string(self.path(year, day, resource)).ok()?;
+        let meta = fs::read_to_string(self.meta_path(year, day, resource))
+            .map_err(|e| e.to_string())
+            .and_then(Meta::try_from)
+            .unwrap_or_default();
+        Some(Entry { body, meta })
+    }
+
+    pub fn put(
+        &self,
+        year: Year,
+        day: Option<Day>,
+        resource: &str,
+        entry: &Entry,
+    ) -> Result<(), io::Error> {
+        let path = self.path(year, day, resource);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, &entry.body)?;
+        write_meta(&self.meta_path(year, day, resource), &entry.meta)
+    }
+
+    /// Updates the metadata of a cached resource, e.g. after a successful revalidation.
+    pub fn put_meta(
+        &self,
+        year: Year,
+        day: Option<Day>,
+        resource: &str,
+        meta: &Meta,
+    ) -> Result<(), io::Error> {
+        write_meta(&self.meta_path(year, day, resource), meta)
+    }
+
+    /// Removes a cached resource. Missing entries are not an error.
+    pub fn invalidate(&self, year: Year, day: Option<Day>, resource: &str) {
+        let _ = fs::remove_file(self.path(year, day, resource));
+        let _ = fs::remove_file(self.meta_path(year, day, resource));
+    }
+
+    fn dir(&self, year: Year, day: Option<Day>) -> PathBuf {
+        let dir = self.root.join(year.to_string());
+        match day {
+            Some(day) => dir.join(day.to_string()),
+            None => dir,
+        }
+    }
+
+    fn path(&self, year: Year, day: Option<Day>, resource: &str) -> PathBuf {
+        self.dir(year, day).join(resource)
+    }
+
+    fn meta_path(&self, year: Year, day: Option<Day>, resource: &str) -> PathBuf {
+        self.dir(year, day).join(format!("{resource}.meta.json"))
+    }
+}
+
+fn write_meta(path: &Path, meta: &Meta) -> Result<(), io::Error> {
+    let json = JsonValue::from(meta);
+    let mut file = fs::File::create(path)?;
+    json.format_to(&mut file)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Meta> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Meta) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (key, header) in [
+            ("etag", &value.etag),
+            ("last_modified", &value.last_modified),
+        ] {
+            map.insert(
+                key.into(),
+                match header {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        map.insert(
+            "fetched_at".into(),
+            JsonValue::Number(value.fetched_at as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Meta {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let header = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        Ok(Meta {
+            etag: header("etag"),
+            last_modified: header("last_modified"),
+            fetched_at: json
+                .get("fetched_at")
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or("expected meta.fetched_at to be a number.")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Cache, Entry, Meta};
+    use crate::{day, year};
+    use std::{env, fs, process};
+
+    fn get_cache(name: &str) -> Cache {
+        let root = env::temp_dir().join(format!("aoc-cache-{}-{name}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        Cache::new(root)
+    }
+
+    fn get_mock_entry() -> Entry {
+        Entry {
+            body: "1\n2\n".into(),
+            meta: Meta {
+                etag: Some("\"abc\"".into()),
+                last_modified: None,
+                fetched_at: 1_733_000_000,
+            },
+        }
+    }
+
+    #[test]
+    fn handles_missing_entries() {
+        let cache = get_cache("missing");
+        assert_eq!(cache.get(year!(2025), Some(day!(1)), "input"), None);
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let cache = get_cache("roundtrip");
+        let entry = get_mock_entry();
+        cache
+            .put(year!(2025), Some(day!(1)), "input", &entry)
+            .unwrap();
+        assert_eq!(cache.get(year!(2025), Some(day!(1)), "input"), Some(entry));
+        assert_eq!(cache.get(year!(2024), Some(day!(1)), "input"), None);
+        assert_eq!(cache.get(year!(2025), Some(day!(2)), "input"), None);
+    }
+
+    #[test]
+    fn stores_resources_without_day() {
+        let cache = get_cache("calendar");
+        let entry = get_mock_entry();
+        cache.put(year!(2025), None, "calendar", &entry).unwrap();
+        assert_eq!(cache.get(year!(2025), None, "calendar"), Some(entry));
+    }
+
+    #[test]
+    fn invalidates_entries() {
+        let cache = get_cache("invalidate");
+        cache
+            .put(year!(2025), Some(day!(1)), "puzzle", &get_mock_entry())
+            .unwrap();
+        cache.invalidate(year!(2025), Some(day!(1)), "puzzle");
+        assert_eq!(cache.get(year!(2025), Some(day!(1)), "puzzle"), None);
+    }
+}
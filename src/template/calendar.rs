@@ -0,0 +1,114 @@
+/// Per-year model of the event: which days exist, how many parts they have and when they unlock.
+use crate::template::{AllDays, Day, Year};
+
+/// Puzzles unlock at midnight in the server's timezone (UTC-5).
+const UNLOCK_UTC_HOUR: u64 = 5;
+
+/// The first year with a shortened event of 12 days.
+const FIRST_SHORT_YEAR: u16 = 2025;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calendar {
+    year: Year,
+}
+
+impl Calendar {
+    pub fn new(year: Year) -> Self {
+        Self { year }
+    }
+
+    /// The last day of the event: the 25th until 2024, the 12th from 2025 on.
+    pub fn last_day(self) -> Day {
+        let last = if self.year.into_inner() >= FIRST_SHORT_YEAR {
+            12
+        } else {
+            25
+        };
+        // NOTE: both values are valid days.
+        Day::new(last).unwrap()
+    }
+
+    /// An iterator over every day of the event.
+    pub fn days(self) -> AllDays {
+        AllDays::until(self.last_day())
+    }
+
+    pub fn contains(self, day: Day) -> bool {
+        day <= self.last_day()
+    }
+
+    /// Number of parts of a puzzle. The final day only has one part.
+    pub fn parts(self, day: Day) -> u8 {
+        if day == self.last_day() { 1 } else { 2 }
+    }
+
+    /// Unix timestamp (in seconds) at which the puzzle of `day` unlocks.
+    pub fn unlocks_at(self, day: Day) -> u64 {
+        let days = days_from_civil(self.year.into_inner().into(), 12, day.into_inner().into());
+        days * 86_400 + UNLOCK_UTC_HOUR * 3_600
+    }
+
+    pub fn is_unlocked(self, day: Day, now: u64) -> bool {
+        now >= self.unlocks_at(day)
+    }
+
+    /// Day of the week of `day` in December, from `0` for Monday to `6` for Sunday.
+    pub fn weekday(self, day: Day) -> u8 {
+        let days = days_from_civil(self.year.into_inner().into(), 12, day.into_inner().into());
+        // NOTE: the unix epoch was a Thursday.
+        ((days + 3) % 7) as u8
+    }
+}
+
+/// Days since the unix epoch for a date in the proleptic gregorian calendar.
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Calendar;
+    use crate::{day, year};
+
+    #[test]
+    fn knows_the_length_of_events() {
+        assert_eq!(Calendar::new(year!(2024)).last_day(), day!(25));
+        assert_eq!(Calendar::new(year!(2025)).last_day(), day!(12));
+        assert_eq!(Calendar::new(year!(2025)).days().count(), 12);
+        assert!(Calendar::new(year!(2024)).contains(day!(13)));
+        assert!(!Calendar::new(year!(2025)).contains(day!(13)));
+    }
+
+    #[test]
+    fn final_day_has_one_part() {
+        let calendar = Calendar::new(year!(2025));
+        assert_eq!(calendar.parts(day!(11)), 2);
+        assert_eq!(calendar.parts(day!(12)), 1);
+        assert_eq!(Calendar::new(year!(2024)).parts(day!(12)), 2);
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        let calendar = Calendar::new(year!(2025));
+        // 2025-12-01T05:00:00Z
+        assert_eq!(calendar.unlocks_at(day!(1)), 1_764_565_200);
+        assert!(!calendar.is_unlocked(day!(1), 1_764_565_199));
+        assert!(calendar.is_unlocked(day!(1), 1_764_565_200));
+    }
+
+    #[test]
+    fn computes_weekdays() {
+        // 2025-12-01 is a Monday, 2024-12-25 a Wednesday.
+        assert_eq!(Calendar::new(year!(2025)).weekday(day!(1)), 0);
+        assert_eq!(Calendar::new(year!(2025)).weekday(day!(7)), 6);
+        assert_eq!(Calendar::new(year!(2024)).weekday(day!(25)), 2);
+    }
+}
@@ -0,0 +1,418 @@
+/// Renders benchmark timings as a self-contained SVG chart, e.g. `data/2025/benchmarks.svg`.
+///
+/// Every day gets a bar for part 1 and part 2 on a logarithmic scale, so microsecond and second
+/// solutions fit in the same chart. With at least two entries in the benchmark history, a second
+/// panel plots the total time of the stored timings after every stored run.
+use std::{fmt::Write, fs, io, path::Path};
+
+use crate::template::{
+    Year,
+    history::Entry,
+    protocol::Status,
+    timings::{Measurement, Timings, variant_file_name},
+};
+
+static CHART_FILE_STEM: &str = "benchmarks";
+
+const MARGIN_LEFT: f64 = 64.0;
+const MARGIN_RIGHT: f64 = 16.0;
+const MARGIN_TOP: f64 = 48.0;
+const PLOT_HEIGHT: f64 = 240.0;
+const GROUP_WIDTH: f64 = 36.0;
+const BAR_WIDTH: f64 = 12.0;
+const TREND_HEIGHT: f64 = 120.0;
+/// Space below a panel for the labels of the x axis.
+const AXIS_HEIGHT: f64 = 40.0;
+
+const PART_1_COLOR: &str = "#4e79a7";
+const PART_2_COLOR: &str = "#f28e2b";
+const FAILURE_COLOR: &str = "#e15759";
+const GRID_COLOR: &str = "#d0d0d0";
+const TEXT_STYLE: &str = "font-family=\"sans-serif\" font-size=\"11\" fill=\"#555\"";
+
+/// Writes the chart of `year` to its data folder and returns its path relative to the project root.
+pub fn store_file(
+    year: Year,
+    variant: Option<&str>,
+    timings: &Timings,
+    history: &[Entry],
+) -> Result<String, io::Error> {
+    let dir = year.data_dir();
+    fs::create_dir_all(&dir)?;
+    let path = Path::new(&dir).join(variant_file_name(CHART_FILE_STEM, variant, "svg"));
+    fs::write(&path, render(year, timings, history))?;
+    Ok(path.display().to_string())
+}
+
+pub fn render(year: Year, timings: &Timings, history: &[Entry]) -> String {
+    let days = timings.data.len().max(1);
+    #[allow(clippy::cast_precision_loss)]
+    let plot_width = days as f64 * GROUP_WIDTH;
+    let width = MARGIN_LEFT + plot_width + MARGIN_RIGHT;
+
+    let has_trend = history.len() > 1;
+    let mut height = MARGIN_TOP + PLOT_HEIGHT + AXIS_HEIGHT;
+    if has_trend {
+        height += TREND_HEIGHT + AXIS_HEIGHT;
+    }
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">"
+    );
+    let _ = writeln!(
+        svg,
+        "<rect width=\"{width}\" height=\"{height}\" fill=\"#fff\"/>"
+    );
+    let _ = writeln!(
+        svg,
+        "<text x=\"{MARGIN_LEFT}\" y=\"20\" font-family=\"sans-serif\" font-size=\"14\" font-weight=\"bold\" fill=\"#333\">{year} Benchmarks</text>"
+    );
+    draw_legend(&mut svg, MARGIN_LEFT, 36.0);
+    draw_bars(&mut svg, timings, plot_width);
+
+    if has_trend {
+        draw_trend(
+            &mut svg,
+            history,
+            MARGIN_TOP + PLOT_HEIGHT + AXIS_HEIGHT,
+            plot_width,
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn draw_legend(svg: &mut String, x: f64, y: f64) {
+    for (i, (label, color)) in [("Part 1", PART_1_COLOR), ("Part 2", PART_2_COLOR)]
+        .into_iter()
+        .enumerate()
+    {
+        #[allow(clippy::cast_precision_loss)]
+        let x = x + i as f64 * 64.0;
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{x}\" y=\"{}\" width=\"10\" height=\"10\" fill=\"{color}\"/>",
+            y - 9.0
+        );
+        let _ = writeln!(
+            svg,
+            "<text x=\"{}\" y=\"{y}\" {TEXT_STYLE}>{label}</text>",
+            x + 14.0
+        );
+    }
+}
+
+/// Log scale over whole decades of nanoseconds, covering all timings.
+struct Scale {
+    low: i32,
+    high: i32,
+}
+
+impl Scale {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn new(nanos: impl Iterator<Item = u64>) -> Self {
+        let (min, max) = nanos.fold((u64::MAX, 0), |(min, max), x| (min.min(x), max.max(x)));
+        if max == 0 {
+            return Self { low: 0, high: 3 };
+        }
+
+        let low = (min.max(1) as f64).log10().floor() as i32;
+        let high = (max.max(1) as f64).log10().ceil() as i32;
+        Self {
+            low,
+            high: high.max(low + 1),
+        }
+    }
+
+    /// Fraction of the plot height, from `0.0` at the bottom to `1.0` at the top.
+    #[allow(clippy::cast_precision_loss)]
+    fn fraction(&self, nanos: u64) -> f64 {
+        let value = (nanos.max(1) as f64).log10();
+        ((value - f64::from(self.low)) / f64::from(self.high - self.low)).clamp(0.0, 1.0)
+    }
+}
+
+fn draw_bars(svg: &mut String, timings: &Timings, plot_width: f64) {
+    let scale = Scale::new(
+        timings
+            .data
+            .iter()
+            .flat_map(|t| [t.part_1, t.part_2])
+            .flatten()
+            .map(|m| m.nanos),
+    );
+    let bottom = MARGIN_TOP + PLOT_HEIGHT;
+
+    for exponent in scale.low..=scale.high {
+        let y = bottom
+            - PLOT_HEIGHT * f64::from(exponent - scale.low) / f64::from(scale.high - scale.low);
+        let _ = writeln!(
+            svg,
+            "<line x1=\"{MARGIN_LEFT}\" y1=\"{y:.1}\" x2=\"{:.1}\" y2=\"{y:.1}\" stroke=\"{GRID_COLOR}\"/>",
+            MARGIN_LEFT + plot_width
+        );
+        let _ = writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\" {TEXT_STYLE}>{}</text>",
+            MARGIN_LEFT - 6.0,
+            y + 4.0,
+            decade_label(exponent)
+        );
+    }
+
+    for (i, timing) in timings.data.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let group_x = MARGIN_LEFT + i as f64 * GROUP_WIDTH;
+        let parts = [
+            (timing.part_1, timing.part_1_failure, PART_1_COLOR),
+            (timing.part_2, timing.part_2_failure, PART_2_COLOR),
+        ];
+
+        for (j, (measurement, failure, color)) in parts.into_iter().enumerate() {
+            #[allow(clippy::cast_precision_loss)]
+            let x = group_x + 5.0 + j as f64 * (BAR_WIDTH + 2.0);
+            draw_bar(svg, &scale, x, measurement, failure, color);
+        }
+
+        let _ = writeln!(
+            svg,
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" {TEXT_STYLE}>{}</text>",
+            group_x + GROUP_WIDTH / 2.0,
+            bottom + 16.0,
+            timing.day.into_inner()
+        );
+    }
+
+    let _ = writeln!(
+        svg,
+        "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" {TEXT_STYLE}>Day</text>",
+        MARGIN_LEFT + plot_width / 2.0,
+        bottom + 32.0
+    );
+}
+
+fn draw_bar(
+    svg: &mut String,
+    scale: &Scale,
+    x: f64,
+    measurement: Option<Measurement>,
+    failure: Option<Status>,
+    color: &str,
+) {
+    let bottom = MARGIN_TOP + PLOT_HEIGHT;
+
+    match (measurement, failure) {
+        (_, Some(failure)) => {
+            let _ = writeln!(
+                svg,
+                "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" font-family=\"sans-serif\" font-size=\"11\" fill=\"{FAILURE_COLOR}\"><title>{}</title>✘</text>",
+                x + BAR_WIDTH / 2.0,
+                bottom - 4.0,
+                failure.as_str()
+            );
+        }
+        (Some(measurement), None) => {
+            let height = PLOT_HEIGHT * scale.fraction(measurement.nanos);
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{x:.1}\" y=\"{:.1}\" width=\"{BAR_WIDTH}\" height=\"{height:.1}\" fill=\"{color}\"><title>{measurement}</title></rect>",
+                bottom - height
+            );
+        }
+        (None, None) => {}
+    }
+}
+
+/// Plots the total time of the stored timings after every history entry, oldest first, on a linear scale.
+fn draw_trend(svg: &mut String, history: &[Entry], top: f64, plot_width: f64) {
+    // an entry only holds the days benched in its run, e.g. just the new day of an incremental run.
+    // Merged with the entries before it, every point is the total the readme showed after that run.
+    let mut stored = Timings::default();
+    let totals: Vec<f64> = history
+        .iter()
+        .map(|e| {
+            stored = stored.merge(&e.timings);
+            stored.total_millis()
+        })
+        .collect();
+    let max = totals.iter().copied().fold(0.0, f64::max).max(f64::EPSILON);
+    let bottom = top + TREND_HEIGHT;
+
+    let _ = writeln!(
+        svg,
+        "<text x=\"{MARGIN_LEFT}\" y=\"{:.1}\" {TEXT_STYLE}>Total after every stored run (max {max:.2}ms)</text>",
+        top - 8.0
+    );
+    let _ = writeln!(
+        svg,
+        "<line x1=\"{MARGIN_LEFT}\" y1=\"{bottom:.1}\" x2=\"{:.1}\" y2=\"{bottom:.1}\" stroke=\"{GRID_COLOR}\"/>",
+        MARGIN_LEFT + plot_width
+    );
+
+    #[allow(clippy::cast_precision_loss)]
+    let step = plot_width / (totals.len() - 1) as f64;
+    let points: Vec<(f64, f64)> = totals
+        .iter()
+        .enumerate()
+        .map(|(i, total)| {
+            #[allow(clippy::cast_precision_loss)]
+            let x = MARGIN_LEFT + i as f64 * step;
+            (x, bottom - TREND_HEIGHT * total / max)
+        })
+        .collect();
+
+    let polyline = points
+        .iter()
+        .map(|(x, y)| format!("{x:.1},{y:.1}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let _ = writeln!(
+        svg,
+        "<polyline points=\"{polyline}\" fill=\"none\" stroke=\"{PART_1_COLOR}\" stroke-width=\"2\"/>"
+    );
+
+    for ((x, y), (entry, total)) in points.iter().zip(history.iter().zip(&totals)) {
+        let commit = entry
+            .commit
+            .as_deref()
+            .map_or("unknown commit", |c| &c[..c.len().min(7)]);
+        let _ = writeln!(
+            svg,
+            "<circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"3\" fill=\"{PART_1_COLOR}\"><title>{commit}: {total:.2}ms</title></circle>"
+        );
+    }
+}
+
+/// Label of a gridline at `10^exponent` nanoseconds, e.g. `10µs`.
+fn decade_label(exponent: i32) -> String {
+    let units = ["ns", "µs", "ms", "s"];
+    let unit = usize::try_from(exponent.max(0) / 3)
+        .unwrap_or_default()
+        .min(units.len() - 1);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    let value = 10_u64.pow((exponent.max(0) - unit as i32 * 3) as u32);
+    format!("{value}{}", units[unit])
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Scale, decade_label, render};
+    use crate::{
+        day,
+        template::{
+            history::Entry,
+            protocol::Status,
+            timings::{Measurement, Timing, Timings},
+        },
+        year,
+    };
+
+    fn measurement(nanos: u64) -> Option<Measurement> {
+        Some(Measurement {
+            nanos,
+            samples: 100,
+        })
+    }
+
+    fn get_mock_timings() -> Timings {
+        let timing = |day, part_1, part_2| Timing {
+            day,
+            parse: None,
+            parse_stats: None,
+            part_1,
+            part_2,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse_failure: None,
+            part_1_failure: None,
+            part_2_failure: None,
+            parse_alloc: None,
+            part_1_alloc: None,
+            part_2_alloc: None,
+        };
+
+        let mut failed = timing(day!(3), measurement(2_000_000), None);
+        failed.part_2_failure = Some(Status::Timeout);
+
+        Timings {
+            data: vec![
+                timing(day!(1), measurement(500), measurement(80_000)),
+                timing(day!(2), measurement(1_200_000), measurement(3_000_000_000)),
+                failed,
+            ],
+        }
+    }
+
+    fn get_mock_entry(commit: &str, timings: Timings) -> Entry {
+        Entry {
+            timestamp: 1_760_000_000,
+            commit: Some(commit.into()),
+            dirty: false,
+            rustc: None,
+            machine: "0123456789abcdef".into(),
+            timings,
+        }
+    }
+
+    #[test]
+    fn labels_decades() {
+        assert_eq!(decade_label(0), "1ns");
+        assert_eq!(decade_label(2), "100ns");
+        assert_eq!(decade_label(4), "10µs");
+        assert_eq!(decade_label(9), "1s");
+        assert_eq!(decade_label(11), "100s");
+    }
+
+    #[test]
+    fn scales_to_whole_decades() {
+        let scale = Scale::new([500, 3_000_000_000].into_iter());
+        assert_eq!((scale.low, scale.high), (2, 10));
+        assert_eq!(scale.fraction(100), 0.0);
+        assert_eq!(scale.fraction(10_000_000_000), 1.0);
+        assert!((scale.fraction(1_000_000) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn renders_bars_per_part() {
+        let svg = render(year!(2025), &get_mock_timings(), &[]);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains("2025 Benchmarks"));
+        // five bars, and a marker for the part that timed out.
+        assert_eq!(svg.matches("<rect x=").count(), 5 + 2);
+        assert!(svg.contains("<title>timeout</title>✘"));
+        assert!(svg.contains("<title>3.0s</title>"));
+        assert!(!svg.contains("<polyline"));
+    }
+
+    #[test]
+    fn renders_trend_from_history() {
+        let history = [
+            get_mock_entry("aaaaaaa111", get_mock_timings()),
+            get_mock_entry("bbbbbbb222", get_mock_timings()),
+        ];
+        let svg = render(year!(2025), &get_mock_timings(), &history);
+        assert!(svg.contains("<polyline"));
+        assert_eq!(svg.matches("<circle").count(), 2);
+        assert!(svg.contains("<title>bbbbbbb: "));
+    }
+
+    #[test]
+    fn renders_trend_of_merged_timings() {
+        let mut partial = get_mock_timings();
+        partial.data.truncate(1);
+
+        let history = [
+            get_mock_entry("aaaaaaa111", get_mock_timings()),
+            get_mock_entry("bbbbbbb222", partial),
+        ];
+        let svg = render(year!(2025), &get_mock_timings(), &history);
+        assert!(svg.contains("<title>aaaaaaa: 3003.28ms</title>"));
+        assert!(svg.contains("<title>bbbbbbb: 3003.28ms</title>"));
+    }
+}
@@ -0,0 +1,656 @@
+/// Built-in HTTP client for the Advent of Code website.
+///
+/// The session cookie is read from the `AOC_SESSION` (or `ADVENT_OF_CODE_SESSION`) environment variable,
+/// or from a session file: `AOC_SESSION_FILE`, `~/.adventofcode.session` or `~/.config/adventofcode.session`.
+/// The base URL can be overridden with `AOC_BASE_URL`, e.g. to point the client to a local mock server.
+///
+/// Clients created with `from_env` cache responses in `data/cache` and wait at least
+/// `rate_limit::DEFAULT_INTERVAL` between two requests, across invocations.
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::{
+    Calendar, Day, PuzzleId, Year,
+    cache::{self, Cache, Entry, Meta},
+    html,
+    rate_limit::{self, RateLimiter},
+    submissions,
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust (built-in client)";
+
+/// Puzzle descriptions change when part one is solved, the calendar whenever a star is earned.
+/// Both are invalidated after submitting, so a short TTL only matters for changes made elsewhere.
+const PAGE_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+/// How long a cached resource may be served without asking the server.
+#[derive(Clone, Copy)]
+enum Freshness {
+    /// The resource never changes once it is available, e.g. puzzle inputs.
+    Immutable,
+    MaxAge(Duration),
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// No session cookie was found in the environment or in a session file.
+    MissingSession,
+    /// The server did not accept the session cookie.
+    SessionExpired,
+    /// The puzzle has not been unlocked yet.
+    NotUnlocked,
+    /// The server asked us to slow down.
+    RateLimited,
+    BadStatus(u16),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION or create the file \"~/.adventofcode.session\"."
+            ),
+            ClientError::SessionExpired => write!(
+                f,
+                "the session cookie was rejected, it has probably expired. Please refresh it."
+            ),
+            ClientError::NotUnlocked => write!(f, "this puzzle has not been unlocked yet."),
+            ClientError::RateLimited => {
+                write!(f, "too many requests, please wait a bit before retrying.")
+            }
+            ClientError::BadStatus(status) => {
+                write!(f, "the server responded with status {status}.")
+            }
+            ClientError::Transport(e) => write!(f, "request failed: {e}"),
+            ClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for ClientError {}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(e: ureq::Error) -> Self {
+        ClientError::Transport(e.to_string())
+    }
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    cache: Option<Cache>,
+    limiter: RateLimiter,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            cache: None,
+            limiter: RateLimiter::disabled(),
+        }
+    }
+
+    /// Caches responses in `cache`, separately for every session, see [`Cache::for_session`].
+    pub fn with_cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache.for_session(&self.session));
+        self
+    }
+
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    /// Creates a client configured from the environment, see the module documentation.
+    pub fn from_env() -> Result<Self, ClientError> {
+        let session = read_session()?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let state_path = Path::new(cache::CACHE_DIR).join(".last-request");
+
+        Ok(Self::new(&base_url, &session)
+            .with_cache(Cache::new(cache::CACHE_DIR))
+            .with_rate_limiter(RateLimiter::new(
+                rate_limit::DEFAULT_INTERVAL,
+                Some(state_path),
+            )))
+    }
+
+    /// Fetches the input of `puzzle`.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, ClientError> {
+        ensure_unlocked(puzzle)?;
+        self.get(
+            puzzle.year,
+            Some(puzzle.day),
+            "input.txt",
+            &format!("{}/input", get_puzzle_path(puzzle)),
+            Freshness::Immutable,
+        )
+    }
+
+    /// Fetches the description of `puzzle`, converted to markdown.
+    pub fn puzzle(&self, puzzle: PuzzleId) -> Result<String, ClientError> {
+        ensure_unlocked(puzzle)?;
+        let page = self.get(
+            puzzle.year,
+            Some(puzzle.day),
+            "puzzle.html",
+            &get_puzzle_path(puzzle),
+            Freshness::MaxAge(PAGE_MAX_AGE),
+        )?;
+        let nodes = html::parse(&page);
+        let articles = html::find_all(&nodes, "article", Some("day-desc"));
+
+        Ok(articles
+            .into_iter()
+            .map(|article| html::to_markdown(std::slice::from_ref(article)))
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    /// Submits an answer and returns the text of the server's response.
+    pub fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<String, ClientError> {
+        let url = format!("{}{}/answer", self.base_url, get_puzzle_path(puzzle));
+        let part = part.to_string();
+
+        self.limiter.wait();
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", &format!("session={}", self.session))
+            .send_form([("level", part.as_str()), ("answer", answer)])?;
+
+        let page = read_body(response)?;
+
+        // a submission may unlock part two and earn a star.
+        if let Some(cache) = &self.cache {
+            cache.invalidate(puzzle.year, Some(puzzle.day), "puzzle.html");
+            cache.invalidate(puzzle.year, None, "calendar.html");
+        }
+
+        let nodes = html::parse(&page);
+        let article = html::find_all(&nodes, "article", None);
+
+        Ok(html::to_text(
+            article
+                .first()
+                .map_or(&nodes[..], |a| std::slice::from_ref(*a)),
+        ))
+    }
+
+    /// Fetches the number of stars collected for each day of `year` from the calendar page.
+    pub fn stars(&self, year: Year) -> Result<Vec<(Day, u8)>, ClientError> {
+        let page = self.get(
+            year,
+            None,
+            "calendar.html",
+            &format!("/{year}"),
+            Freshness::MaxAge(PAGE_MAX_AGE),
+        )?;
+        let nodes = html::parse(&page);
+
+        // the event page only links to the login page when the session is not valid.
+        if html::find_attrs(&nodes, "href")
+            .iter()
+            .any(|(_, href)| href.ends_with("/auth/login"))
+        {
+            if let Some(cache) = &self.cache {
+                cache.invalidate(year, None, "calendar.html");
+            }
+            return Err(ClientError::SessionExpired);
+        }
+
+        Ok(parse_calendar(&nodes))
+    }
+
+    /// Fetches `path`, serving it from the cache while it is fresh and revalidating it otherwise.
+    fn get(
+        &self,
+        year: Year,
+        day: Option<Day>,
+        resource: &str,
+        path: &str,
+        freshness: Freshness,
+    ) -> Result<String, ClientError> {
+        let cached = self
+            .cache
+            .as_ref()
+            .and_then(|cache| cache.get(year, day, resource));
+
+        if let Some(entry) = &cached
+            && is_fresh(&entry.meta, freshness, submissions::now())
+        {
+            return Ok(entry.body.clone());
+        }
+
+        let mut request = self
+            .agent
+            .get(&format!("{}{path}", self.base_url))
+            .header("Cookie", &format!("session={}", self.session));
+
+        if let Some(entry) = &cached {
+            if let Some(etag) = &entry.meta.etag {
+                request = request.header("If-None-Match", etag);
+            }
+            if let Some(last_modified) = &entry.meta.last_modified {
+                request = request.header("If-Modified-Since", last_modified);
+            }
+        }
+
+        self.limiter.wait();
+        let response = request.call()?;
+
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(String::from)
+        };
+
+        let meta = Meta {
+            etag: header("etag"),
+            last_modified: header("last-modified"),
+            fetched_at: submissions::now(),
+        };
+
+        let Some(cache) = &self.cache else {
+            return read_body(response);
+        };
+
+        if response.status().as_u16() == 304
+            && let Some(entry) = cached
+        {
+            let meta = Meta {
+                etag: meta.etag.or(entry.meta.etag),
+                last_modified: meta.last_modified.or(entry.meta.last_modified),
+                ..meta
+            };
+            if let Err(e) = cache.put_meta(year, day, resource, &meta) {
+                eprintln!("Failed to update cache for \"{resource}\": {e}");
+            }
+            return Ok(entry.body);
+        }
+
+        let entry = Entry {
+            body: read_body(response)?,
+            meta,
+        };
+        if let Err(e) = cache.put(year, day, resource, &entry) {
+            eprintln!("Failed to cache \"{resource}\": {e}");
+        }
+
+        Ok(entry.body)
+    }
+}
+
+fn is_fresh(meta: &Meta, freshness: Freshness, now: u64) -> bool {
+    match freshness {
+        Freshness::Immutable => true,
+        Freshness::MaxAge(max_age) => now.saturating_sub(meta.fetched_at) < max_age.as_secs(),
+    }
+}
+
+fn read_body(mut response: ureq::http::Response<ureq::Body>) -> Result<String, ClientError> {
+    match response.status().as_u16() {
+        200..=299 => Ok(response.body_mut().read_to_string()?),
+        400 | 401 | 403 => Err(ClientError::SessionExpired),
+        404 => Err(ClientError::NotUnlocked),
+        429 => Err(ClientError::RateLimited),
+        status => Err(ClientError::BadStatus(status)),
+    }
+}
+
+/// Reads stars from the `aria-label`s of calendar links, e.g. `aria-label="Day 8, two stars"`.
+fn parse_calendar(nodes: &[html::Node]) -> Vec<(Day, u8)> {
+    let mut stars: Vec<(Day, u8)> = html::find_attrs(nodes, "aria-label")
+        .into_iter()
+        .filter_map(|(_, label)| {
+            let rest = label.strip_prefix("Day ")?;
+            let (day, rest) = rest.split_once(',').unwrap_or((rest, ""));
+            let day = day.trim().parse::<Day>().ok()?;
+            let count = if rest.contains("two stars") {
+                2
+            } else if rest.contains("one star") {
+                1
+            } else {
+                0
+            };
+            Some((day, count))
+        })
+        .collect();
+
+    stars.sort_unstable();
+    stars.dedup_by_key(|(day, _)| *day);
+    stars
+}
+
+/// Avoids requesting puzzles before they unlock, which the server asks clients not to do.
+fn ensure_unlocked(puzzle: PuzzleId) -> Result<(), ClientError> {
+    if Calendar::new(puzzle.year).is_unlocked(puzzle.day, submissions::now()) {
+        Ok(())
+    } else {
+        Err(ClientError::NotUnlocked)
+    }
+}
+
+fn get_puzzle_path(puzzle: PuzzleId) -> String {
+    format!("/{}/day/{}", puzzle.year, puzzle.day.into_inner())
+}
+
+fn read_session() -> Result<String, ClientError> {
+    for var in ["AOC_SESSION", "ADVENT_OF_CODE_SESSION"] {
+        if let Ok(session) = env::var(var)
+            && !session.trim().is_empty()
+        {
+            return Ok(session);
+        }
+    }
+
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok();
+    let candidates: Vec<PathBuf> = env::var("AOC_SESSION_FILE")
+        .ok()
+        .map(PathBuf::from)
+        .into_iter()
+        .chain(home.iter().flat_map(|home| {
+            let home = Path::new(home);
+            [
+                home.join(".adventofcode.session"),
+                home.join(".config").join("adventofcode.session"),
+            ]
+        }))
+        .collect();
+
+    candidates
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or(ClientError::MissingSession)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AocClient, ClientError, Day};
+    use crate::{
+        day,
+        template::{
+            PuzzleId,
+            cache::{Cache, Entry, Meta},
+        },
+        year,
+    };
+    use std::{
+        env, fs,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        process,
+        thread::{self, JoinHandle},
+    };
+
+    fn get_puzzle(day: u8) -> PuzzleId {
+        PuzzleId::new(year!(2025), Day::new(day).unwrap())
+    }
+
+    fn get_cache(name: &str) -> Cache {
+        let root = env::temp_dir().join(format!("aoc-client-cache-{}-{name}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        Cache::new(root)
+    }
+
+    /// Serves one canned response per connection and returns the received requests.
+    fn mock_server(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+                let mut body_buf = vec![0; content_length];
+                reader.read_exact(&mut body_buf).unwrap();
+                request.push_str(&String::from_utf8_lossy(&body_buf));
+                requests.push(request);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nETag: \"mock\"\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+
+            requests
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn downloads_inputs() {
+        let (base_url, server) = mock_server(vec![(200, "1\n2\n3\n")]);
+        let client = AocClient::new(&base_url, "abc");
+        assert_eq!(client.input(get_puzzle(8)).unwrap(), "1\n2\n3\n");
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2025/day/8/input "));
+        assert!(
+            requests[0]
+                .to_ascii_lowercase()
+                .contains("cookie: session=abc")
+        );
+    }
+
+    #[test]
+    fn serves_inputs_from_cache() {
+        let (base_url, server) = mock_server(vec![(200, "1\n2\n3\n")]);
+        let client = AocClient::new(&base_url, "abc").with_cache(get_cache("input"));
+        assert_eq!(client.input(get_puzzle(8)).unwrap(), "1\n2\n3\n");
+        // the server only accepts one connection, a second request would fail.
+        assert_eq!(client.input(get_puzzle(8)).unwrap(), "1\n2\n3\n");
+        assert_eq!(server.join().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn caches_inputs_per_session() {
+        let (base_url, server) = mock_server(vec![(200, "1\n"), (200, "2\n")]);
+        let client = AocClient::new(&base_url, "abc").with_cache(get_cache("session"));
+        assert_eq!(client.input(get_puzzle(8)).unwrap(), "1\n");
+
+        let root = env::temp_dir().join(format!("aoc-client-cache-{}-session", process::id()));
+        let other = AocClient::new(&base_url, "def").with_cache(Cache::new(root));
+        assert_eq!(other.input(get_puzzle(8)).unwrap(), "2\n");
+        assert_eq!(client.input(get_puzzle(8)).unwrap(), "1\n");
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(
+            requests[1]
+                .to_ascii_lowercase()
+                .contains("cookie: session=def")
+        );
+    }
+
+    #[test]
+    fn revalidates_stale_pages() {
+        let cache = get_cache("puzzle");
+        cache
+            .for_session("abc")
+            .put(
+                year!(2025),
+                Some(day!(8)),
+                "puzzle.html",
+                &Entry {
+                    body: r#"<article class="day-desc"><p>Cached</p></article>"#.into(),
+                    meta: Meta {
+                        etag: Some("\"mock\"".into()),
+                        last_modified: None,
+                        fetched_at: 0,
+                    },
+                },
+            )
+            .unwrap();
+
+        let (base_url, server) = mock_server(vec![(304, "")]);
+        let client = AocClient::new(&base_url, "abc").with_cache(cache);
+        assert_eq!(client.puzzle(get_puzzle(8)).unwrap(), "Cached\n");
+        // the revalidated page is fresh again and served without a request.
+        assert_eq!(client.puzzle(get_puzzle(8)).unwrap(), "Cached\n");
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(
+            requests[0]
+                .to_ascii_lowercase()
+                .contains("if-none-match: \"mock\"")
+        );
+    }
+
+    #[test]
+    fn converts_puzzles_to_markdown() {
+        let (base_url, server) = mock_server(vec![(
+            200,
+            r#"<html><body><main><article class="day-desc"><h2>--- Day 8 ---</h2><p>Hi <em>there</em>.</p></article><p>Your puzzle answer was <code>1</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2></article></main></body></html>"#,
+        )]);
+        let client = AocClient::new(&base_url, "abc");
+        assert_eq!(
+            client.puzzle(get_puzzle(8)).unwrap(),
+            "## --- Day 8 ---\n\nHi *there*.\n\n## --- Part Two ---\n"
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, server) = mock_server(vec![(
+            200,
+            "<main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main>",
+        )]);
+        let client = AocClient::new(&base_url, "abc");
+        assert_eq!(
+            client.submit(get_puzzle(8), 2, "1234").unwrap(),
+            "That's the right answer! You are one gold star closer."
+        );
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2025/day/8/answer "));
+        assert!(requests[0].ends_with("level=2&answer=1234"));
+    }
+
+    #[test]
+    fn fetches_stars() {
+        let (base_url, server) = mock_server(vec![(
+            200,
+            r#"<pre class="calendar"><a aria-label="Day 1, two stars" href="/2025/day/1">1</a><a aria-label="Day 2, one star" href="/2025/day/2">2</a><a aria-label="Day 3" href="/2025/day/3">3</a></pre><a href="/2025/auth/logout">Log Out</a>"#,
+        )]);
+        let client = AocClient::new(&base_url, "abc");
+        assert_eq!(
+            client.stars(year!(2025)).unwrap(),
+            vec![(day!(1), 2), (day!(2), 1), (day!(3), 0)]
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn maps_status_codes_to_errors() {
+        let (base_url, server) = mock_server(vec![
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+            ),
+            (
+                404,
+                "Please don't repeatedly request this endpoint before it unlocks!",
+            ),
+            (429, ""),
+            (500, ""),
+        ]);
+        let client = AocClient::new(&base_url, "abc");
+        assert!(matches!(
+            client.input(get_puzzle(1)),
+            Err(ClientError::SessionExpired)
+        ));
+        assert!(matches!(
+            client.input(get_puzzle(1)),
+            Err(ClientError::NotUnlocked)
+        ));
+        assert!(matches!(
+            client.input(get_puzzle(1)),
+            Err(ClientError::RateLimited)
+        ));
+        assert!(matches!(
+            client.input(get_puzzle(1)),
+            Err(ClientError::BadStatus(500))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn refuses_locked_puzzles() {
+        // no server is listening, a request would fail with a transport error.
+        let client = AocClient::new("http://127.0.0.1:1", "abc");
+        let puzzle = PuzzleId::new(year!(9999), day!(1));
+        assert!(matches!(
+            client.input(puzzle),
+            Err(ClientError::NotUnlocked)
+        ));
+        assert!(matches!(
+            client.puzzle(puzzle),
+            Err(ClientError::NotUnlocked)
+        ));
+    }
+
+    #[test]
+    fn detects_expired_sessions_on_calendar() {
+        let (base_url, server) =
+            mock_server(vec![(200, r#"<a href="/2025/auth/login">[Log In]</a>"#)]);
+        let client = AocClient::new(&base_url, "abc");
+        assert!(matches!(
+            client.stars(year!(2025)),
+            Err(ClientError::SessionExpired)
+        ));
+        server.join().unwrap();
+    }
+}
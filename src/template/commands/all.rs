@@ -1,5 +1,30 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{Year, all_days, run_multi::run_multi};
+
+/// Options of `all`, shared by the `advent_of_code` and `all-days` binaries.
+pub struct Args {
+    pub is_release: bool,
+    pub jobs: usize,
+}
+
+pub fn parse_args(args: &mut pico_args::Arguments) -> Result<Args, pico_args::Error> {
+    Ok(Args {
+        is_release: args.contains("--release"),
+        jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+    })
+}
+
+pub fn handle(year: Year, args: &Args) {
+    let summary = run_multi(
+        year,
+        &all_days(year).collect(),
+        args.is_release,
+        false,
+        args.jobs,
+    );
+
+    if !summary.failed_checks.is_empty() || !summary.failures.is_empty() {
+        process::exit(1);
+    }
 }
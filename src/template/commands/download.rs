@@ -1,16 +1,67 @@
-use crate::template::{Day, aoc_cli};
-use std::process;
+use crate::template::{PuzzleId, client::AocClient, examples};
+use std::{fs, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
-        process::exit(1);
+pub fn handle(puzzle: PuzzleId) {
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to set up client: {e}");
+            process::exit(1);
+        }
+    };
+
+    let input_path = puzzle.data_path("inputs", "txt");
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    for path in [&input_path, &puzzle_path] {
+        if let Some(parent) = path.parent()
+            && let Err(e) = fs::create_dir_all(parent)
+        {
+            eprintln!("Failed to create directory \"{}\": {e}", parent.display());
+            process::exit(1);
+        }
+    }
+
+    // inputs never change, keep the one that is already there.
+    let has_input = fs::metadata(&input_path).is_ok_and(|m| m.len() > 0);
+
+    let result = if has_input {
+        Ok(())
+    } else {
+        client
+            .input(puzzle)
+            .and_then(|input| Ok(fs::write(&input_path, input)?))
     }
+    .and_then(|()| client.puzzle(puzzle))
+    .and_then(|description| {
+        fs::write(&puzzle_path, &description)?;
+        Ok(description)
+    });
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let description = match result {
+        Ok(description) => description,
+        Err(e) => {
+            eprintln!("Failed to download {puzzle}: {e}");
+            process::exit(1);
+        }
     };
+
+    println!("---");
+    if has_input {
+        println!(
+            "🎄 Input \"{}\" already exists, skipping.",
+            input_path.display()
+        );
+    } else {
+        println!(
+            "🎄 Successfully wrote input to \"{}\".",
+            input_path.display()
+        );
+    }
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+
+    examples::apply(puzzle, &description);
 }
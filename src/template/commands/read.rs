@@ -1,17 +1,37 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::{Day, aoc_cli};
+use crate::template::{PuzzleId, client::AocClient, examples};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
+pub fn handle(puzzle: PuzzleId) {
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to set up client: {e}");
+            process::exit(1);
+        }
+    };
+
+    let description = match client.puzzle(puzzle) {
+        Ok(description) => description,
+        Err(e) => {
+            eprintln!("Failed to read {puzzle}: {e}");
+            process::exit(1);
+        }
+    };
+
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+    if let Err(e) = puzzle_path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&puzzle_path, &description))
+    {
         eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            "Failed to write puzzle to \"{}\": {e}",
+            puzzle_path.display()
         );
-        process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    println!("{description}");
+
+    examples::apply(puzzle, &description);
 }
@@ -1,15 +1,16 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{Calendar, PuzzleId, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+fn safe_create_file(path: impl AsRef<Path>, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,7 +20,7 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: impl AsRef<Path>) -> Result<File, std::io::Error> {
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,10 +28,59 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Removes `part_two` and its test from the module template, for puzzles with a single part.
+fn remove_part_two(template: &str) -> String {
+    let mut module = template.replace("solution!(%DAY_NUMBER%);", "solution!(%DAY_NUMBER%, 1);");
+
+    for (start, end) in [
+        ("pub fn part_two", "\n}\n\n"),
+        ("\n    #[test]\n    fn test_part_two", "\n    }\n"),
+    ] {
+        if let Some(from) = module.find(start)
+            && let Some(len) = module[from..].find(end)
+        {
+            module.replace_range(from..from + len + end.len(), "");
+        }
+    }
+
+    module
+}
+
+/// Adds a [`crate::template::Params`] constant to the module template and passes it to both parts.
+/// Tests call the parts with the example value.
+fn add_params(template: &str) -> String {
+    template
+        .replacen(
+            "solution!(%DAY_NUMBER%",
+            "solution!(%DAY_NUMBER%, params = PARAMS",
+            1,
+        )
+        .replacen(
+            "\n\npub fn",
+            "\n\nconst PARAMS: Params<Config> = Params {\n    example: Config {},\n    real: Config {},\n};\n\npub struct Config {}\n\npub fn",
+            1,
+        )
+        .replacen(";\n", ";\n\nuse advent_of_code::template::Params;\n", 1)
+        .replace("(input: &str)", "(input: &str, config: &Config)")
+        .replace(
+            "(&advent_of_code::template::read_file(\"examples\", PUZZLE))",
+            "(\n            &advent_of_code::template::read_file(\"examples\", PUZZLE),\n            &PARAMS.example,\n        )",
+        )
+}
+
+pub fn handle(puzzle: PuzzleId, overwrite: bool, params: bool) {
+    let input_path = puzzle.data_path("inputs", "txt");
+    let example_path = puzzle.data_path("examples", "txt");
+    let module_path = puzzle.bin_path();
+
+    for path in [&input_path, &example_path] {
+        if let Some(parent) = path.parent()
+            && let Err(e) = fs::create_dir_all(parent)
+        {
+            eprintln!("Failed to create directory \"{}\": {e}", parent.display());
+            process::exit(1);
+        }
+    }
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,9 +90,19 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
+    let mut template = if Calendar::new(puzzle.year).parts(puzzle.day) == 1 {
+        remove_part_two(MODULE_TEMPLATE)
+    } else {
+        MODULE_TEMPLATE.to_string()
+    };
+
+    if params {
+        template = add_params(&template);
+    }
+
     match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        template
+            .replace("%DAY_NUMBER%", &puzzle.day.into_inner().to_string())
             .as_bytes(),
     ) {
         Ok(()) => {
@@ -56,7 +116,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", input_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -66,7 +126,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match create_file(&example_path) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!("Created empty example file \"{}\"", example_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -75,5 +135,40 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    let year_arg = if Year::from_env() == Some(puzzle.year) {
+        String::new()
+    } else {
+        format!(" --year {}", puzzle.year)
+    };
+    println!(
+        "🎄 Type `cargo solve {}{year_arg}` to run your solution.",
+        puzzle.day
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{MODULE_TEMPLATE, add_params, remove_part_two};
+
+    #[test]
+    fn removes_part_two() {
+        let module = remove_part_two(MODULE_TEMPLATE);
+        assert!(module.starts_with("advent_of_code::solution!(%DAY_NUMBER%, 1);"));
+        assert!(module.contains("fn part_one"));
+        assert!(module.contains("fn test_part_one"));
+        assert!(!module.contains("part_two"));
+        assert!(module.ends_with("    }\n}\n"));
+    }
+
+    #[test]
+    fn adds_params() {
+        let module = add_params(&remove_part_two(MODULE_TEMPLATE));
+        assert!(module.starts_with(
+            "advent_of_code::solution!(%DAY_NUMBER%, params = PARAMS, 1);\n\nuse advent_of_code::template::Params;\n\nconst PARAMS"
+        ));
+        assert!(module.contains("pub fn part_one(input: &str, config: &Config) -> Option<u64>"));
+        assert!(module.contains("&PARAMS.example,"));
+    }
 }
@@ -1,9 +1,55 @@
-use std::process::{Command, Stdio};
+use std::{
+    collections::BTreeMap,
+    env,
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
 
-use crate::template::Day;
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, PuzzleId,
+    input::Source,
+    protocol::{self, PartResult},
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// How often `--watch` checks the watched files for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Takes `--example [N]` out of the arguments of `solve`, since pico-args has no options with an
+/// optional value. The argument after `--example` is only used as example number if it is a number.
+pub fn take_example_arg(args: &mut Vec<OsString>) -> Option<Source> {
+    if args.first().is_none_or(|command| command != "solve") {
+        return None;
+    }
+
+    let index = args.iter().position(|arg| arg == "--example")?;
+    args.remove(index);
+
+    let number = args
+        .get(index)
+        .and_then(|arg| arg.to_str())
+        .and_then(|arg| arg.parse::<u8>().ok());
+
+    if number.is_some() {
+        args.remove(index);
+    }
+
+    Some(Source::Example(number))
+}
+
+fn get_args(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    accept: bool,
+    examples: bool,
+    source: &Source,
+) -> Vec<String> {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
 
     if dhat {
         cmd_args.extend([
@@ -23,12 +69,365 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+    if accept {
+        cmd_args.push("--accept".to_string());
+    }
+
+    if examples {
+        cmd_args.push("--examples".to_string());
+    }
+
+    cmd_args.extend(source.to_args());
+
+    cmd_args
+}
+
+/// Builds and runs the solution, appending its result records to `results_path` if given.
+fn run(cmd_args: &[String], results_path: Option<&Path>) {
+    let mut cmd = Command::new("cargo");
+    cmd.args(cmd_args)
         .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .stderr(Stdio::inherit());
+
+    if let Some(results_path) = results_path {
+        cmd.env(protocol::RESULTS_PATH_ENV, results_path);
+    }
+
+    cmd.spawn().unwrap().wait().unwrap();
+}
+
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    accept: bool,
+    examples: bool,
+    source: &Source,
+) {
+    run(
+        &get_args(puzzle, release, dhat, submit_part, accept, examples, source),
+        None,
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Runs the solution of `puzzle` and runs it again whenever its module, the library or its data files change.
+/// After every run, the answers are compared with the previous successful run.
+pub fn watch(puzzle: PuzzleId, release: bool, dhat: bool, examples: bool, source: &Source) {
+    let cmd_args = get_args(puzzle, release, dhat, None, false, examples, source);
+    let results_path = env::temp_dir().join(format!("aoc-watch-{}-{puzzle}.jsonl", process::id()));
+
+    let mut snapshot = Snapshot::take(puzzle, source);
+    let mut previous: Vec<PartResult> = vec![];
+
+    loop {
+        let _ = fs::remove_file(&results_path);
+        run(&cmd_args, Some(&results_path));
+        let results = protocol::read_results(&results_path);
+
+        if !previous.is_empty() && !results.is_empty() {
+            print_diff(&diff_answers(&previous, &results));
+        }
+
+        // keep the answers of the last run that got far enough to emit them, e.g. when the build fails.
+        if !results.is_empty() {
+            previous = results;
+        }
+
+        println!("\n{ANSI_ITALIC}Watching for changes...{ANSI_RESET}");
+
+        let (next, changed) = wait_for_change(puzzle, source, &snapshot);
+        snapshot = next;
+
+        println!(
+            "\n{ANSI_BOLD}Changed:{ANSI_RESET} {}\n",
+            changed
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+}
+
+/// Blocks until a watched file changes and returns the new snapshot and the changed files.
+fn wait_for_change(
+    puzzle: PuzzleId,
+    source: &Source,
+    snapshot: &Snapshot,
+) -> (Snapshot, Vec<PathBuf>) {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        if Snapshot::take(puzzle, source) != *snapshot {
+            // editors often write a file in multiple steps, let them finish first.
+            thread::sleep(POLL_INTERVAL);
+            let next = Snapshot::take(puzzle, source);
+            let changed = snapshot.changes(&next);
+            return (next, changed);
+        }
+    }
+}
+
+/// Modification times of the files a solution depends on.
+#[derive(Debug, PartialEq, Eq)]
+struct Snapshot(BTreeMap<PathBuf, SystemTime>);
+
+impl Snapshot {
+    fn take(puzzle: PuzzleId, source: &Source) -> Self {
+        let mut files = BTreeMap::new();
+
+        for path in [
+            PathBuf::from(puzzle.bin_path()),
+            PathBuf::from("Cargo.toml"),
+        ] {
+            insert_file(&mut files, &path);
+        }
+
+        // the library, every module of it is shared by all solutions.
+        insert_dir(&mut files, Path::new("src"), &|path| {
+            path != Path::new("src").join("bin")
+        });
+
+        insert_file(&mut files, &puzzle.data_path("inputs", "txt"));
+        if let Source::File(path) = source {
+            insert_file(&mut files, path);
+        }
+
+        let day = puzzle.day.to_string();
+        let examples = PathBuf::from(puzzle.year.data_dir()).join("examples");
+        insert_dir(&mut files, &examples, &|path| is_example_file(path, &day));
+
+        Self(files)
+    }
+
+    /// Files that were added, removed or modified in `next`.
+    fn changes(&self, next: &Snapshot) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = self
+            .0
+            .iter()
+            .filter(|(path, modified)| next.0.get(*path) != Some(modified))
+            .map(|(path, _)| path.clone())
+            .collect();
 
-    cmd.wait().unwrap();
+        changed.extend(
+            next.0
+                .keys()
+                .filter(|path| !self.0.contains_key(*path))
+                .cloned(),
+        );
+
+        changed
+    }
+}
+
+fn insert_file(files: &mut BTreeMap<PathBuf, SystemTime>, path: &Path) {
+    if let Ok(modified) = fs::metadata(path).and_then(|m| m.modified()) {
+        files.insert(path.to_path_buf(), modified);
+    }
+}
+
+/// Inserts the files in `dir` and its subdirectories that pass `filter`.
+fn insert_dir(
+    files: &mut BTreeMap<PathBuf, SystemTime>,
+    dir: &Path,
+    filter: &dyn Fn(&Path) -> bool,
+) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !filter(&path) {
+            continue;
+        }
+        if path.is_dir() {
+            insert_dir(files, &path, filter);
+        } else {
+            insert_file(files, &path);
+        }
+    }
+}
+
+/// Matches the example files of a day, e.g. `08.txt`, `08-2.txt` or `08.json` for day `08`.
+fn is_example_file(path: &Path, day: &str) -> bool {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .is_some_and(|stem| stem == day || stem.starts_with(&format!("{day}-")))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A part whose answer differs from the previous run.
+#[derive(Debug, PartialEq, Eq)]
+struct AnswerChange {
+    part: u8,
+    previous: String,
+    current: String,
+}
+
+fn describe(result: Option<&PartResult>) -> String {
+    match result {
+        None => "missing".into(),
+        Some(result) => match &result.answer {
+            Some(answer) => answer.clone(),
+            None => result.status.as_str().into(),
+        },
+    }
+}
+
+fn diff_answers(previous: &[PartResult], current: &[PartResult]) -> Vec<AnswerChange> {
+    let find =
+        |results: &[PartResult], part: u8| results.iter().rev().find(|r| r.part == part).cloned();
+
+    (1..=2)
+        .filter_map(|part| {
+            let (before, after) = (find(previous, part), find(current, part));
+            if before.is_none() && after.is_none() {
+                return None;
+            }
+
+            let (previous, current) = (describe(before.as_ref()), describe(after.as_ref()));
+            (previous != current).then_some(AnswerChange {
+                part,
+                previous,
+                current,
+            })
+        })
+        .collect()
+}
+
+/// Multi-line answers, e.g. letters drawn with `#`, do not fit on one line and are only marked.
+fn shorten(answer: &str) -> &str {
+    if answer.contains('\n') { "▼" } else { answer }
+}
+
+fn print_diff(changes: &[AnswerChange]) {
+    if changes.is_empty() {
+        println!("\n{ANSI_ITALIC}Answers unchanged since the last run.{ANSI_RESET}");
+        return;
+    }
+
+    println!("\n{ANSI_BOLD}Answers changed since the last run:{ANSI_RESET}");
+    for change in changes {
+        println!(
+            "  Part {}: {} → {ANSI_BOLD}{}{ANSI_RESET}",
+            change.part,
+            shorten(&change.previous),
+            shorten(&change.current)
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{AnswerChange, diff_answers, is_example_file};
+    use crate::template::{
+        answers::Check,
+        protocol::{PartResult, Status},
+    };
+
+    fn result(part: u8, status: Status, answer: Option<&str>) -> PartResult {
+        PartResult {
+            part,
+            status,
+            answer: answer.map(Into::into),
+            nanos: 0,
+            samples: 1,
+            stats: None,
+            check: Check::Unknown,
+            message: None,
+            alloc: None,
+        }
+    }
+
+    #[test]
+    fn diffs_answers() {
+        let previous = vec![
+            result(0, Status::Solved, None),
+            result(1, Status::Solved, Some("42")),
+            result(2, Status::Unsolved, None),
+        ];
+        let current = vec![
+            result(0, Status::Solved, None),
+            result(1, Status::Solved, Some("42")),
+            result(2, Status::Solved, Some("7")),
+        ];
+
+        assert_eq!(
+            diff_answers(&previous, &current),
+            vec![AnswerChange {
+                part: 2,
+                previous: "unsolved".into(),
+                current: "7".into(),
+            }]
+        );
+        assert_eq!(diff_answers(&current, &current), vec![]);
+    }
+
+    #[test]
+    fn diffs_missing_parts() {
+        let previous = vec![result(1, Status::Solved, Some("42"))];
+        let current = vec![result(1, Status::Panic, None)];
+
+        assert_eq!(
+            diff_answers(&previous, &current),
+            vec![AnswerChange {
+                part: 1,
+                previous: "42".into(),
+                current: "panic".into(),
+            }]
+        );
+        assert_eq!(
+            diff_answers(&previous, &[]),
+            vec![AnswerChange {
+                part: 1,
+                previous: "42".into(),
+                current: "missing".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn diffs_multi_line_answers() {
+        let previous = vec![result(1, Status::Solved, Some("#..#\n####"))];
+        let current = vec![result(1, Status::Solved, Some("#..#\n#..#"))];
+
+        assert_eq!(
+            diff_answers(&previous, &current),
+            vec![AnswerChange {
+                part: 1,
+                previous: "#..#\n####".into(),
+                current: "#..#\n#..#".into(),
+            }]
+        );
+        assert_eq!(diff_answers(&previous, &previous), vec![]);
+    }
+
+    #[test]
+    fn matches_example_files() {
+        assert!(is_example_file(
+            Path::new("data/2025/examples/08.txt"),
+            "08"
+        ));
+        assert!(is_example_file(
+            Path::new("data/2025/examples/08-2.txt"),
+            "08"
+        ));
+        assert!(is_example_file(
+            Path::new("data/2025/examples/08.json"),
+            "08"
+        ));
+        assert!(!is_example_file(
+            Path::new("data/2025/examples/18.txt"),
+            "08"
+        ));
+    }
 }
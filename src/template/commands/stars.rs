@@ -0,0 +1,134 @@
+use crate::template::{
+    ANSI_BOLD, ANSI_RESET, Calendar, Year,
+    progress::{DayProgress, PartState, Progress},
+    readme_stars,
+    submissions::now,
+};
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Prints the progress of `year` as a calendar, and writes it to the readme badge with `update_readme`.
+pub fn handle(year: Year, update_readme: bool) {
+    let progress = Progress::read(year, now());
+
+    println!("{}", render_calendar(&progress));
+
+    if update_readme {
+        match readme_stars::update(&progress) {
+            Ok(()) => println!("Updated the stars badge."),
+            Err(e) => eprintln!("Failed to update the stars badge: {e:?}"),
+        }
+    }
+}
+
+/// Renders a December calendar with the state of both parts of every day, starting on a Monday.
+fn render_calendar(progress: &Progress) -> String {
+    let calendar = Calendar::new(progress.year);
+
+    let mut lines = vec![
+        format!(
+            "{ANSI_BOLD}{} {}/{} ★{ANSI_RESET}",
+            progress.year,
+            progress.stars(),
+            progress.max_stars()
+        ),
+        String::new(),
+        WEEKDAYS
+            .iter()
+            .map(|day| format!("{day:<7}"))
+            .collect::<String>()
+            .trim_end()
+            .to_string(),
+    ];
+
+    let offset = progress
+        .days
+        .first()
+        .map_or(0, |d| usize::from(calendar.weekday(d.day)));
+    let mut cells: Vec<String> = vec![" ".repeat(7); offset];
+    cells.extend(progress.days.iter().map(format_day));
+
+    for week in cells.chunks(7) {
+        lines.push(week.concat().trim_end().to_string());
+    }
+
+    lines.push(String::new());
+    lines.push(
+        [
+            PartState::Solved,
+            PartState::Answered,
+            PartState::Attempted,
+            PartState::Open,
+        ]
+        .iter()
+        .map(|state| format!("{} {}", state.symbol(), describe(*state)))
+        .collect::<Vec<_>>()
+        .join("  "),
+    );
+
+    lines.join("\n")
+}
+
+fn format_day(progress: &DayProgress) -> String {
+    format!(
+        "{:>2} {}{}  ",
+        progress.day.into_inner(),
+        progress.part_1.symbol(),
+        progress.part_2.symbol()
+    )
+}
+
+fn describe(state: PartState) -> &'static str {
+    match state {
+        PartState::Solved => "solved",
+        PartState::Answered => "unconfirmed",
+        PartState::Attempted => "wrong answer",
+        PartState::Open => "open",
+        PartState::Locked => "locked",
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render_calendar;
+    use crate::{
+        template::{
+            Day,
+            progress::{DayProgress, PartState, Progress},
+        },
+        year,
+    };
+
+    #[test]
+    fn renders_calendar() {
+        let progress = Progress {
+            year: year!(2025),
+            days: (1..=12)
+                .map(|day| DayProgress {
+                    day: Day::new(day).unwrap(),
+                    part_1: match day {
+                        1..=2 => PartState::Solved,
+                        3 => PartState::Attempted,
+                        _ => PartState::Locked,
+                    },
+                    part_2: match day {
+                        1 => PartState::Solved,
+                        2 => PartState::Answered,
+                        3 => PartState::Open,
+                        _ => PartState::Locked,
+                    },
+                })
+                .collect(),
+        };
+
+        let rendered = render_calendar(&progress);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert!(lines[0].contains("2025 3/24 ★"));
+        assert_eq!(lines[2], "Mon    Tue    Wed    Thu    Fri    Sat    Sun");
+        // 2025-12-01 is a Monday.
+        assert_eq!(lines[3], " 1 ★★   2 ★☆   3 ✘·   4      5      6      7");
+        assert_eq!(lines[4], " 8      9     10     11     12");
+    }
+}
@@ -1,40 +1,196 @@
 use std::collections::HashSet;
+use std::ffi::OsString;
+use std::process;
 
+use crate::template::export::{Export, Format};
+use crate::template::history::{self, Baseline, Entry};
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{Calendar, Day, Year, all_days, readme_benchmarks, registry};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Slowdown of a part, in percent, above which `--compare` reports a regression.
+pub const DEFAULT_THRESHOLD: f64 = 5.0;
+
+/// Takes `--compare [ref]` out of the arguments of `time`, since pico-args has no options with an
+/// optional value. The argument after `--compare` is only used as ref if it is not an option or a day.
+pub fn take_compare_arg(args: &mut Vec<OsString>) -> Option<Baseline> {
+    if args.first().is_none_or(|command| command != "time") {
+        return None;
+    }
+
+    let index = args.iter().position(|arg| arg == "--compare")?;
+    args.remove(index);
+
+    let reference = args
+        .get(index)
+        .and_then(|arg| arg.to_str())
+        .filter(|arg| !arg.starts_with('-') && arg.parse::<Day>().is_err())
+        .map(String::from);
+
+    match reference {
+        Some(reference) => {
+            args.remove(index);
+            Some(Baseline::Commit(reference))
+        }
+        None => Some(Baseline::Latest),
+    }
+}
+
+/// Options of `time`, shared by the `advent_of_code` and `all-days` binaries.
+pub struct Args {
+    pub day: Option<Day>,
+    pub run_all: bool,
+    pub store: bool,
+    pub variant: Option<String>,
+    pub compare: Option<Baseline>,
+    pub threshold: f64,
+    pub export: Option<Export>,
+    pub jobs: usize,
+    pub allow_parallel: bool,
+}
+
+/// Parses the options of `time`. `compare` has to be taken out of the arguments first, see [`take_compare_arg`].
+pub fn parse_args(
+    year: Year,
+    args: &mut pico_args::Arguments,
+    compare: Option<Baseline>,
+) -> Result<Args, pico_args::Error> {
+    let run_all = args.contains("--all");
+    let store = args.contains("--store");
+    let variant = args.opt_value_from_str("--variant")?;
+    let threshold = args
+        .opt_value_from_str("--threshold")?
+        .unwrap_or(DEFAULT_THRESHOLD);
+    let out = args.opt_value_from_str("--out")?;
+    let export = args
+        .opt_value_from_str::<_, Format>("--export")?
+        .map(|format| Export::new(year, format, out));
+    let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+    let allow_parallel = args.contains("--allow-parallel");
+
+    Ok(Args {
+        // NOTE: free arguments are parsed after all options.
+        day: args.opt_free_from_str()?,
+        run_all,
+        store,
+        variant,
+        compare,
+        threshold,
+        export,
+        jobs,
+        allow_parallel,
+    })
+}
+
+/// Benches run one day at a time, since concurrent days skew each other's timings.
+/// `allow_parallel` opts into `jobs` concurrent days, e.g. on a machine with isolated cores.
+///
+/// With `compare`, the timings are compared with an entry of the benchmark history and the process
+/// exits with a non-zero status if a part got slower by more than `threshold` percent.
+/// With `export`, the timings of this run are also written to a file.
+/// A `variant`, e.g. `debug`, keeps its timings, history and readme sections apart from the default ones.
+pub fn handle(year: Year, args: Args) {
+    let Args {
+        day,
+        run_all,
+        store,
+        variant,
+        compare,
+        threshold,
+        export,
+        jobs,
+        allow_parallel,
+    } = args;
+    let variant = variant.as_deref();
+
+    let last_day = Calendar::new(year).last_day();
+    if day.is_some_and(|day| day > last_day) {
+        eprintln!(
+            "Error: the {year} event only has {} puzzles.",
+            last_day.into_inner()
+        );
+        process::exit(1);
+    }
+
+    let stored_timings = Timings::read_from_file(year, variant);
+    let calendar = Calendar::new(year);
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
-                all_days().collect()
+            if run_all || compare.is_some() {
+                all_days(year).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                all_days(year)
+                    .filter(|day| !stored_timings.is_day_complete(*day, calendar.parts(*day)))
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let jobs = if jobs > 1 && !allow_parallel {
+        eprintln!("Benchmarks run serially, pass `--allow-parallel` to use {jobs} jobs.\n");
+        1
+    } else {
+        jobs
+    };
+
+    // with a registry, the current build is benched, e.g. a debug build for a `debug` variant.
+    let is_release = !registry::is_installed() || !cfg!(debug_assertions);
+    let timings = run_multi(year, &days_to_run, is_release, true, jobs)
+        .timings
+        .unwrap();
+
+    if let Some(export) = export {
+        match export.write(year, &timings) {
+            Ok(()) => println!(
+                "\nExported {} benchmarks to {}.",
+                export.format, export.path
+            ),
+            Err(e) => eprintln!("\nFailed to export benchmarks to {}: {e}", export.path),
+        }
+    }
+
+    let mut regressions = 0;
+
+    if let Some(baseline) = compare {
+        let entries = history::read(year, variant);
+        match history::find_baseline(&entries, &baseline) {
+            Some(entry) => {
+                let deltas = history::compare(&entry.timings, &timings);
+                regressions = history::print_comparison(entry, &deltas, threshold / 100.0);
+            }
+            None => {
+                eprintln!("\nNo benchmark history to compare with, run `cargo time --store` first.")
+            }
+        }
+    }
 
     if store {
+        if let Err(e) = history::append(year, variant, &Entry::new(timings.clone())) {
+            eprintln!("Failed to append to the benchmark history: {e}");
+        }
+
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year, variant).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, variant, merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
+            Err(readme_benchmarks::Error::Parser(e)) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
+            }
             Err(_) => {
                 eprintln!("Failed to store updated benchmarks.");
             }
         }
     }
+
+    if regressions > 0 {
+        eprintln!("\n{regressions} part(s) regressed by more than {threshold}%.");
+        process::exit(1);
+    }
 }
@@ -0,0 +1,278 @@
+/// Named example inputs with their expected answers, listed in `data/{year}/examples/{day}.json`:
+///
+/// ```json
+/// [
+///   { "name": "small", "file": "08.txt", "part_1": "40", "part_2": "25272" },
+///   { "name": "line", "input": "1,2,3\n", "part_1": 6 }
+/// ]
+/// ```
+///
+/// A case reads its input from a `file` in the examples folder or has it `input` inline.
+/// Missing or `null` answers are not checked. JSON numbers are floats, so integer answers beyond
+/// ±2^53 have to be written as strings to keep every digit. `build.rs` generates a test per case, and
+/// `cargo solve <day> --examples` runs every case with the normal runner output.
+use std::{
+    collections::HashMap,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    process,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    ANSI_BOLD, ANSI_RESET, PuzzleId,
+    answers::{Answers, Check},
+    protocol::{self, PartResult, Status},
+    runner,
+};
+
+/// The largest integer that a JSON number holds exactly.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExampleCase {
+    pub name: String,
+    pub input: String,
+    pub expected: Answers,
+}
+
+/// Reads the cases of `puzzle`. Without a manifest, the example file is a single case without answers.
+pub fn read(puzzle: PuzzleId) -> Result<Vec<ExampleCase>, String> {
+    let manifest = get_manifest_path(puzzle);
+
+    if !manifest.exists() {
+        let path = puzzle.data_path("examples", "txt");
+        let input = fs::read_to_string(&path)
+            .map_err(|e| format!("could not read \"{}\": {e}", path.display()))?;
+        return Ok(vec![ExampleCase {
+            name: puzzle.day.to_string(),
+            input,
+            expected: Answers::default(),
+        }]);
+    }
+
+    let contents = fs::read_to_string(&manifest)
+        .map_err(|e| format!("could not read \"{}\": {e}", manifest.display()))?;
+
+    parse(&contents, |file| {
+        let path = PathBuf::from(puzzle.year.data_dir())
+            .join("examples")
+            .join(file);
+        fs::read_to_string(&path).map_err(|e| format!("could not read \"{}\": {e}", path.display()))
+    })
+}
+
+fn get_manifest_path(puzzle: PuzzleId) -> PathBuf {
+    puzzle.data_path("examples", "json")
+}
+
+fn parse(
+    contents: &str,
+    read_file: impl Fn(&str) -> Result<String, String>,
+) -> Result<Vec<ExampleCase>, String> {
+    let json = JsonValue::from_str(contents).or(Err("not valid JSON file."))?;
+    let cases = json
+        .get::<Vec<JsonValue>>()
+        .ok_or("expected JSON document to be an array.")?;
+
+    cases
+        .iter()
+        .enumerate()
+        .map(|(i, case)| {
+            let case = case
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or(format!("expected case {i} to be an object."))?;
+
+            let name = case
+                .get("name")
+                .and_then(|v| v.get::<String>())
+                .ok_or(format!("expected case {i} to have a name."))?
+                .clone();
+
+            let input = match (
+                case.get("input").and_then(|v| v.get::<String>()),
+                case.get("file").and_then(|v| v.get::<String>()),
+            ) {
+                (Some(input), None) => input.clone(),
+                (None, Some(file)) => read_file(file)?,
+                _ => {
+                    return Err(format!(
+                        "expected case \"{name}\" to have either an input or a file."
+                    ));
+                }
+            };
+
+            let answer = |key: &str| match case.get(key) {
+                None | Some(JsonValue::Null) => Ok(None),
+                Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+                Some(JsonValue::Number(n)) if n.abs() > MAX_SAFE_INTEGER => Err(format!(
+                    "{key} of case \"{name}\" is too large for a JSON number, write it as a string instead."
+                )),
+                #[allow(clippy::cast_possible_truncation)]
+                Some(JsonValue::Number(n)) if n.fract() == 0.0 => Ok(Some((*n as i64).to_string())),
+                Some(_) => Err(format!(
+                    "expected {key} of case \"{name}\" to be null, a string or an integer."
+                )),
+            };
+
+            Ok(ExampleCase {
+                expected: Answers {
+                    part_1: answer("part_1")?,
+                    part_2: answer("part_2")?,
+                },
+                name,
+                input,
+            })
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Runs `case` with the runner in example mode and returns the records of its parts.
+fn run_case(case: &ExampleCase, run: fn(&str)) -> Vec<PartResult> {
+    runner::run_example(case.expected.clone(), || {
+        protocol::collect(|| {
+            // NOTE: the panic hook already printed the message and location.
+            let _ = panic::catch_unwind(AssertUnwindSafe(|| run(&case.input)));
+        })
+    })
+}
+
+/// Describes why a part of a case failed, `None` if it passed.
+/// Parts without an expected answer do not fail, since an example often only applies to one part.
+fn get_failure(result: &PartResult, expected: &Answers) -> Option<String> {
+    let is_expected = match result.part {
+        protocol::PARSE_PART => expected.part_1.is_some() || expected.part_2.is_some(),
+        part => expected.get(part).is_some(),
+    };
+
+    if !is_expected {
+        return None;
+    }
+
+    match (&result.status, &result.check) {
+        (Status::Panic, _) => Some(format!("part {} panicked", result.part)),
+        (Status::Timeout, _) => Some(format!("part {} timed out", result.part)),
+        (_, Check::Fail { expected }) => Some(format!(
+            "part {}: expected {expected}, got {}",
+            result.part,
+            result.answer.as_deref().unwrap_or("None")
+        )),
+        _ => None,
+    }
+}
+
+/// Runs every case of `puzzle` and exits with a non-zero status if one of them failed.
+pub fn run(puzzle: PuzzleId, run: fn(&str)) {
+    let cases = match read(puzzle) {
+        Ok(cases) => cases,
+        Err(e) => {
+            eprintln!("Failed to read examples: {e}");
+            process::exit(1);
+        }
+    };
+
+    let mut failed: Vec<String> = vec![];
+
+    for (i, case) in cases.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}Example {}{ANSI_RESET}", case.name);
+
+        if run_case(case, run)
+            .iter()
+            .any(|r| get_failure(r, &case.expected).is_some())
+        {
+            failed.push(case.name.clone());
+        }
+    }
+
+    if !failed.is_empty() {
+        println!(
+            "\n{ANSI_BOLD}✘ Examples failed:{ANSI_RESET} {}",
+            failed.join(", ")
+        );
+        process::exit(1);
+    }
+}
+
+/// Runs the case `name` of `puzzle` and panics if one of its parts failed, used by the tests generated in `build.rs`.
+pub fn test_case(puzzle: PuzzleId, name: &str, run: fn(&str)) {
+    let cases = read(puzzle).unwrap_or_else(|e| panic!("failed to read examples: {e}"));
+    let case = cases
+        .iter()
+        .find(|case| case.name == name)
+        .unwrap_or_else(|| panic!("no example named \"{name}\"."));
+
+    let failures: Vec<String> = run_case(case, run)
+        .iter()
+        .filter_map(|r| get_failure(r, &case.expected))
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ExampleCase, parse};
+    use crate::template::answers::Answers;
+
+    #[test]
+    fn parses_cases() {
+        let manifest = r#"[
+            { "name": "small", "file": "08.txt", "part_1": "40", "part_2": 25272 },
+            { "name": "inline", "input": "1,2,3\n", "part_2": null }
+        ]"#;
+
+        let cases = parse(manifest, |file| Ok(format!("contents of {file}"))).unwrap();
+        assert_eq!(
+            cases,
+            vec![
+                ExampleCase {
+                    name: "small".into(),
+                    input: "contents of 08.txt".into(),
+                    expected: Answers {
+                        part_1: Some("40".into()),
+                        part_2: Some("25272".into()),
+                    },
+                },
+                ExampleCase {
+                    name: "inline".into(),
+                    input: "1,2,3\n".into(),
+                    expected: Answers::default(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_cases_without_input() {
+        let manifest = r#"[{ "name": "empty", "part_1": "1" }]"#;
+        assert!(parse(manifest, |_| Ok(String::new())).is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_answers() {
+        let manifest = r#"[{ "name": "x", "input": "", "part_1": 1.5 }]"#;
+        assert!(parse(manifest, |_| Ok(String::new())).is_err());
+    }
+
+    #[test]
+    fn rejects_unsafe_integers() {
+        let manifest = r#"[{ "name": "x", "input": "", "part_1": 9007199254740993 }]"#;
+        let err = parse(manifest, |_| Ok(String::new())).unwrap_err();
+        assert!(err.contains("write it as a string"));
+
+        let manifest = r#"[{ "name": "x", "input": "", "part_1": 9007199254740991 }]"#;
+        let cases = parse(manifest, |_| Ok(String::new())).unwrap();
+        assert_eq!(
+            cases[0].expected.part_1.as_deref(),
+            Some("9007199254740991")
+        );
+    }
+}
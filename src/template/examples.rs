@@ -0,0 +1,305 @@
+/// Extracts example inputs and their expected answers from puzzle markdown (see `html::to_markdown`).
+///
+/// The example input is the first code block introduced by a paragraph mentioning an example.
+/// The expected answer is the last emphasized value (``*`42`*``) following it in the same part.
+use std::fs;
+
+use crate::template::PuzzleId;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Examples {
+    pub input: Option<String>,
+    /// Example input of part two, if it differs from the one of part one.
+    pub input_2: Option<String>,
+    pub answer_1: Option<String>,
+    pub answer_2: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Block {
+    Text(String),
+    Code(String),
+}
+
+pub fn extract(markdown: &str) -> Examples {
+    let sections = split_parts(markdown);
+    let mut examples = Examples::default();
+
+    if let Some(part_1) = sections.first() {
+        let blocks = parse_blocks(part_1);
+        let index = find_example(&blocks).or_else(|| {
+            blocks
+                .iter()
+                .position(|block| matches!(block, Block::Code(_)))
+        });
+
+        if let Some(index) = index {
+            if let Block::Code(code) = &blocks[index] {
+                examples.input = Some(code.clone());
+            }
+            examples.answer_1 = find_answer(&blocks[index..]);
+        }
+    }
+
+    if let Some(part_2) = sections.get(1) {
+        let blocks = parse_blocks(part_2);
+        match find_example(&blocks) {
+            Some(index) => {
+                if let Block::Code(code) = &blocks[index] {
+                    examples.input_2 = Some(code.clone());
+                }
+                examples.answer_2 = find_answer(&blocks[index..]);
+            }
+            None => examples.answer_2 = find_answer(&blocks),
+        }
+    }
+
+    examples
+}
+
+/// Writes extracted examples to empty example files and fills the answers of untouched tests.
+pub fn apply(puzzle: PuzzleId, markdown: &str) {
+    let examples = extract(markdown);
+
+    let example_dir = format!("{}/examples", puzzle.year.data_dir());
+
+    for (input, path) in [
+        (&examples.input, format!("{example_dir}/{}.txt", puzzle.day)),
+        (
+            &examples.input_2,
+            format!("{example_dir}/{}-2.txt", puzzle.day),
+        ),
+    ] {
+        let Some(input) = input else {
+            continue;
+        };
+
+        // keep examples that were already extracted or edited by hand.
+        if fs::read_to_string(&path).is_ok_and(|s| !s.trim().is_empty()) {
+            continue;
+        }
+
+        match fs::create_dir_all(&example_dir).and_then(|()| fs::write(&path, format!("{input}\n")))
+        {
+            Ok(()) => println!("🎄 Extracted example to \"{path}\"."),
+            Err(e) => eprintln!("Failed to write example to \"{path}\": {e}"),
+        }
+    }
+
+    let module_path = puzzle.bin_path();
+    let Ok(source) = fs::read_to_string(&module_path) else {
+        return;
+    };
+
+    let filled = fill_test(&source, "test_part_one", examples.answer_1.as_deref(), None);
+    let filled = fill_test(
+        &filled,
+        "test_part_two",
+        examples.answer_2.as_deref(),
+        examples.input_2.as_ref().map(|_| 2),
+    );
+
+    if filled != source {
+        match fs::write(&module_path, filled) {
+            Ok(()) => println!("🎄 Filled in expected example answers in \"{module_path}\"."),
+            Err(e) => eprintln!("Failed to update \"{module_path}\": {e}"),
+        }
+    }
+}
+
+/// Replaces the `assert_eq!(result, None)` placeholder of a scaffolded test with `answer`.
+/// If `part` is set, the test is also switched to the example file of that part.
+fn fill_test(source: &str, test: &str, answer: Option<&str>, part: Option<u8>) -> String {
+    const PLACEHOLDER: &str = "assert_eq!(result, None);";
+
+    let Some(answer) = answer.filter(|a| a.parse::<i128>().is_ok()) else {
+        return source.to_string();
+    };
+
+    let Some(start) = source.find(&format!("fn {test}()")) else {
+        return source.to_string();
+    };
+
+    let end = source[start + 3..]
+        .find("fn ")
+        .map_or(source.len(), |i| start + 3 + i);
+
+    let body = &source[start..end];
+    if !body.contains(PLACEHOLDER) {
+        return source.to_string();
+    }
+
+    let mut body = body.replace(PLACEHOLDER, &format!("assert_eq!(result, Some({answer}));"));
+    if let Some(part) = part {
+        body = body.replace(
+            "read_file(\"examples\", PUZZLE)",
+            &format!("read_file_part(\"examples\", PUZZLE, {part})"),
+        );
+    }
+
+    format!("{}{body}{}", &source[..start], &source[end..])
+}
+
+/// Splits puzzle markdown at its headings, one section per part.
+fn split_parts(markdown: &str) -> Vec<String> {
+    let mut sections: Vec<String> = vec![];
+
+    for line in markdown.lines() {
+        if line.starts_with("## ") || sections.is_empty() {
+            sections.push(String::new());
+        }
+        if let Some(section) = sections.last_mut() {
+            section.push_str(line);
+            section.push('\n');
+        }
+    }
+
+    sections
+}
+
+fn parse_blocks(section: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut current = String::new();
+    let mut in_code = false;
+
+    for line in section.lines() {
+        if line.starts_with("```") {
+            if in_code {
+                blocks.push(Block::Code(current.trim_end_matches('\n').to_string()));
+            } else if !current.trim().is_empty() {
+                blocks.push(Block::Text(current.trim().to_string()));
+            }
+            current.clear();
+            in_code = !in_code;
+            continue;
+        }
+
+        if !in_code && line.trim().is_empty() {
+            if !current.trim().is_empty() {
+                blocks.push(Block::Text(current.trim().to_string()));
+            }
+            current.clear();
+            continue;
+        }
+
+        current.push_str(line);
+        current.push('\n');
+    }
+
+    if !in_code && !current.trim().is_empty() {
+        blocks.push(Block::Text(current.trim().to_string()));
+    }
+
+    blocks
+}
+
+/// Index of the first code block preceded by a paragraph that mentions an example.
+fn find_example(blocks: &[Block]) -> Option<usize> {
+    blocks
+        .windows(2)
+        .position(|pair| match pair {
+            [Block::Text(text), Block::Code(_)] => text.to_lowercase().contains("example"),
+            _ => false,
+        })
+        .map(|i| i + 1)
+}
+
+/// The last emphasized code value in the text blocks.
+fn find_answer(blocks: &[Block]) -> Option<String> {
+    blocks
+        .iter()
+        .filter_map(|block| match block {
+            Block::Text(text) => Some(text),
+            Block::Code(_) => None,
+        })
+        .flat_map(|text| {
+            text.split("*`")
+                .skip(1)
+                .filter_map(|s| s.split_once("`*").map(|(value, _)| value.to_string()))
+                .collect::<Vec<_>>()
+        })
+        .next_back()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Examples, extract, fill_test};
+
+    const TEMPLATE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+    const MARKDOWN: &str = "## --- Day 6: Trash Compactor ---
+
+Some story with a `1` and *emphasis*.
+
+For example, here is a worksheet:
+
+```
+123 328
+ 45 64
+*   +
+```
+
+The first problem is `123 * 45` = *`5535`*.
+
+In this example, the grand total is *`5535`* + *`392`* = *`5927`*.
+
+Solve the problem on the worksheet. What is the grand total?
+
+## --- Part Two ---
+
+Now read it right-to-left. In the example above, the grand total is *`3263827`*.
+
+What is the grand total found by adding together all of the answers?
+";
+
+    #[test]
+    fn extracts_examples() {
+        assert_eq!(
+            extract(MARKDOWN),
+            Examples {
+                input: Some("123 328\n 45 64\n*   +".into()),
+                input_2: None,
+                answer_1: Some("5927".into()),
+                answer_2: Some("3263827".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn extracts_examples_of_part_two() {
+        let puzzle = format!(
+            "{MARKDOWN}\nFor example, consider this other worksheet:\n\n```\n1 2\n```\n\nThis yields *`3`*.\n"
+        );
+        let examples = extract(&puzzle);
+        assert_eq!(examples.input_2, Some("1 2".into()));
+        assert_eq!(examples.answer_2, Some("3".into()));
+    }
+
+    #[test]
+    fn handles_puzzles_without_part_two() {
+        let examples = extract(MARKDOWN.split("## --- Part Two").next().unwrap());
+        assert_eq!(examples.answer_1, Some("5927".into()));
+        assert_eq!(examples.answer_2, None);
+        assert_eq!(extract(""), Examples::default());
+    }
+
+    #[test]
+    fn fills_test_answers() {
+        let filled = fill_test(TEMPLATE, "test_part_one", Some("5927"), None);
+        let filled = fill_test(&filled, "test_part_two", Some("3"), Some(2));
+        assert!(filled.contains("assert_eq!(result, Some(5927));"));
+        assert!(filled.contains("assert_eq!(result, Some(3));"));
+        assert!(filled.contains("read_file(\"examples\", PUZZLE)"));
+        assert!(filled.contains("read_file_part(\"examples\", PUZZLE, 2)"));
+
+        // tests that were already filled in are left alone.
+        assert_eq!(fill_test(&filled, "test_part_one", Some("1"), None), filled);
+        // non-numeric answers can't be filled in.
+        assert_eq!(
+            fill_test(TEMPLATE, "test_part_one", Some("abc"), None),
+            TEMPLATE
+        );
+    }
+}
@@ -0,0 +1,411 @@
+/// Exports benchmark timings for spreadsheets and dashboards, see `cargo time --export`.
+///
+/// Every benched step of a day becomes one row with its mean duration, its stats and heap usage if
+/// they were recorded, and the status of steps that panicked or timed out.
+use std::{collections::HashMap, fmt::Display, fs, io, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    Day, Year,
+    alloc_stats::AllocStats,
+    protocol::{PARSE_PART, Status},
+    stats::{Stats, format_nanos},
+    timings::{Measurement, Timings},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    JsonLines,
+    Markdown,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::JsonLines => "jsonl",
+            Format::Markdown => "md",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(Format::Csv),
+            "jsonl" | "jsonlines" | "json-lines" => Ok(Format::JsonLines),
+            "md" | "markdown" => Ok(Format::Markdown),
+            _ => Err(format!(
+                "unknown export format `{s}`, expected `csv`, `jsonl` or `markdown`."
+            )),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Format::Csv => "CSV",
+            Format::JsonLines => "JSON Lines",
+            Format::Markdown => "Markdown",
+        })
+    }
+}
+
+/// Where `cargo time --export` writes to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Export {
+    pub format: Format,
+    pub path: String,
+}
+
+impl Export {
+    /// Exports to `path`, or to e.g. `data/2025/timings.csv` if no path is given.
+    pub fn new(year: Year, format: Format, path: Option<String>) -> Self {
+        let path =
+            path.unwrap_or_else(|| format!("{}/timings.{}", year.data_dir(), format.extension()));
+        Self { format, path }
+    }
+
+    pub fn write(&self, year: Year, timings: &Timings) -> Result<(), io::Error> {
+        if let Some(dir) = Path::new(&self.path).parent()
+            && !dir.as_os_str().is_empty()
+        {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, render(year, timings, self.format))
+    }
+}
+
+/// Outcome of one step of a day.
+struct Row {
+    day: Day,
+    /// The part, or [`PARSE_PART`] for the parse step.
+    part: u8,
+    status: Status,
+    measurement: Option<Measurement>,
+    stats: Option<Stats>,
+    alloc: Option<AllocStats>,
+}
+
+impl Row {
+    fn step(&self) -> String {
+        if self.part == PARSE_PART {
+            "parse".into()
+        } else {
+            format!("part_{}", self.part)
+        }
+    }
+}
+
+fn rows(timings: &Timings) -> Vec<Row> {
+    let mut rows = vec![];
+
+    for timing in &timings.data {
+        let steps = [
+            (
+                PARSE_PART,
+                timing.parse,
+                timing.parse_stats,
+                timing.parse_alloc,
+                timing.parse_failure,
+            ),
+            (
+                1,
+                timing.part_1,
+                timing.part_1_stats,
+                timing.part_1_alloc,
+                timing.part_1_failure,
+            ),
+            (
+                2,
+                timing.part_2,
+                timing.part_2_stats,
+                timing.part_2_alloc,
+                timing.part_2_failure,
+            ),
+        ];
+
+        for (part, measurement, stats, alloc, failure) in steps {
+            let status = match (failure, measurement) {
+                (Some(failure), _) => failure,
+                (None, Some(_)) => Status::Solved,
+                (None, None) => continue,
+            };
+
+            rows.push(Row {
+                day: timing.day,
+                part,
+                status,
+                measurement,
+                stats,
+                alloc,
+            });
+        }
+    }
+
+    rows
+}
+
+const COLUMNS: [&str; 16] = [
+    "year",
+    "day",
+    "step",
+    "status",
+    "nanos",
+    "samples",
+    "min",
+    "median",
+    "p95",
+    "stddev",
+    "ci_low",
+    "ci_high",
+    "outliers",
+    "allocations",
+    "bytes",
+    "peak_bytes",
+];
+
+/// Numeric columns of a row, `None` where the value was not recorded.
+fn numbers(row: &Row) -> [Option<u64>; 12] {
+    let stats = |f: fn(&Stats) -> u64| row.stats.as_ref().map(f);
+    let alloc = |f: fn(&AllocStats) -> u64| row.alloc.as_ref().map(f);
+
+    [
+        row.measurement.map(|m| m.nanos),
+        row.measurement.map(|m| m.samples),
+        stats(|s| s.min),
+        stats(|s| s.median),
+        stats(|s| s.p95),
+        stats(|s| s.stddev),
+        stats(|s| s.ci_low),
+        stats(|s| s.ci_high),
+        stats(|s| s.outliers),
+        alloc(|a| a.allocations),
+        alloc(|a| a.bytes),
+        alloc(|a| a.peak_bytes),
+    ]
+}
+
+pub fn render(year: Year, timings: &Timings, format: Format) -> String {
+    let rows = rows(timings);
+    match format {
+        Format::Csv => render_csv(year, &rows),
+        Format::JsonLines => render_json_lines(year, &rows),
+        Format::Markdown => render_markdown(year, &rows),
+    }
+}
+
+fn render_csv(year: Year, rows: &[Row]) -> String {
+    let mut lines = vec![COLUMNS.join(",")];
+
+    for row in rows {
+        let mut cells = vec![
+            year.to_string(),
+            row.day.to_string(),
+            row.step(),
+            row.status.as_str().into(),
+        ];
+        cells.extend(
+            numbers(row)
+                .iter()
+                .map(|x| x.map(|x| x.to_string()).unwrap_or_default()),
+        );
+        lines.push(cells.join(","));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn render_json_lines(year: Year, rows: &[Row]) -> String {
+    let mut out = String::new();
+
+    for row in rows {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("year".into(), JsonValue::from(year.to_string()));
+        map.insert("day".into(), JsonValue::from(row.day.to_string()));
+        map.insert("step".into(), JsonValue::from(row.step()));
+        map.insert(
+            "status".into(),
+            JsonValue::from(row.status.as_str().to_string()),
+        );
+
+        for (key, value) in COLUMNS[4..].iter().zip(numbers(row)) {
+            #[allow(clippy::cast_precision_loss)]
+            let value = value.map_or(JsonValue::Null, |x| JsonValue::Number(x as f64));
+            map.insert((*key).into(), value);
+        }
+
+        let line = JsonValue::Object(map)
+            .stringify()
+            .expect("export row is serializable");
+        out.push_str(&line);
+        out.push('\n');
+    }
+
+    out
+}
+
+fn render_markdown(year: Year, rows: &[Row]) -> String {
+    let columns = [
+        "Day",
+        "Step",
+        "Mean",
+        "± Stddev",
+        "Min",
+        "Median",
+        "p95",
+        "95% CI",
+        "Samples",
+        "Outliers",
+        "Heap",
+    ];
+
+    let mut lines = vec![
+        format!("# {year} Benchmarks"),
+        String::new(),
+        format!("| {} |", columns.join(" | ")),
+        format!("| {} |", vec![":---:"; columns.len()].join(" | ")),
+    ];
+
+    let nanos = |x: Option<u64>| x.map_or_else(|| "-".into(), format_nanos);
+    let cell = |x: Option<String>| x.unwrap_or_else(|| "-".into());
+
+    for row in rows {
+        let stats = row.stats;
+        let mean = match row.status {
+            Status::Panic | Status::Timeout => row.status.as_str().into(),
+            _ => nanos(row.measurement.map(|m| m.nanos)),
+        };
+
+        let cells = [
+            row.day.into_inner().to_string(),
+            row.step().replace('_', " "),
+            mean,
+            nanos(stats.map(|s| s.stddev)),
+            nanos(stats.map(|s| s.min)),
+            nanos(stats.map(|s| s.median)),
+            nanos(stats.map(|s| s.p95)),
+            cell(
+                stats.map(|s| format!("{} – {}", format_nanos(s.ci_low), format_nanos(s.ci_high))),
+            ),
+            cell(row.measurement.map(|m| m.samples.to_string())),
+            cell(stats.map(|s| s.outliers.to_string())),
+            cell(row.alloc.map(|a| a.summary())),
+        ];
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Format, render};
+    use crate::{
+        day,
+        template::{
+            alloc_stats::AllocStats,
+            protocol::Status,
+            stats::Stats,
+            timings::{Measurement, Timing, Timings},
+        },
+        year,
+    };
+    use std::{collections::HashMap, str::FromStr};
+    use tinyjson::JsonValue;
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                parse: None,
+                parse_stats: None,
+                part_1: Some(Measurement {
+                    nanos: 74_130,
+                    samples: 1000,
+                }),
+                part_2: None,
+                part_1_stats: Some(Stats {
+                    mean: 74_130,
+                    min: 70_000,
+                    median: 74_000,
+                    p95: 80_000,
+                    stddev: 1_500,
+                    ci_low: 74_000,
+                    ci_high: 74_260,
+                    samples: 1000,
+                    outliers: 4,
+                }),
+                part_2_stats: None,
+                parse_failure: None,
+                part_1_failure: None,
+                part_2_failure: Some(Status::Timeout),
+                parse_alloc: None,
+                part_1_alloc: Some(AllocStats {
+                    allocations: 12,
+                    bytes: 4096,
+                    peak_bytes: 1024,
+                }),
+                part_2_alloc: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(Format::from_str("CSV"), Ok(Format::Csv));
+        assert_eq!(Format::from_str("jsonl"), Ok(Format::JsonLines));
+        assert_eq!(Format::from_str("markdown"), Ok(Format::Markdown));
+        assert!(Format::from_str("xlsx").is_err());
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = render(year!(2025), &get_mock_timings(), Format::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            "year,day,step,status,nanos,samples,min,median,p95,stddev,ci_low,ci_high,outliers,allocations,bytes,peak_bytes"
+        );
+        assert_eq!(
+            lines[1],
+            "2025,01,part_1,solved,74130,1000,70000,74000,80000,1500,74000,74260,4,12,4096,1024"
+        );
+        assert_eq!(lines[2], "2025,01,part_2,timeout,,,,,,,,,,,,");
+    }
+
+    #[test]
+    fn renders_json_lines() {
+        let jsonl = render(year!(2025), &get_mock_timings(), Format::JsonLines);
+        let rows: Vec<JsonValue> = jsonl.lines().map(|l| l.parse().unwrap()).collect();
+        assert_eq!(rows.len(), 2);
+
+        let row = rows[0].get::<HashMap<String, JsonValue>>().unwrap();
+        assert_eq!(row["step"].get::<String>().unwrap(), "part_1");
+        assert_eq!(*row["nanos"].get::<f64>().unwrap(), 74_130.0);
+        assert_eq!(*row["outliers"].get::<f64>().unwrap(), 4.0);
+
+        let row = rows[1].get::<HashMap<String, JsonValue>>().unwrap();
+        assert_eq!(row["status"].get::<String>().unwrap(), "timeout");
+        assert!(row["nanos"].is_null());
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let md = render(year!(2025), &get_mock_timings(), Format::Markdown);
+        assert!(md.starts_with("# 2025 Benchmarks\n"));
+        assert!(md.contains(
+            "| 1 | part 1 | 74.1µs | 1.5µs | 70.0µs | 74.0µs | 80.0µs | 74.0µs – 74.3µs | 1000 | 4 | 12 allocs · 4.0 KiB · peak 1.0 KiB |"
+        ));
+        assert!(md.contains("| 1 | part 2 | timeout | - | - | - | - | - | - | - | - |"));
+    }
+}
@@ -0,0 +1,150 @@
+/// Isolates solution parts from each other: panics are caught per part and a watchdog ends parts that run too long.
+use std::{
+    cell::{Cell, RefCell},
+    env,
+    io::{Write, stdout},
+    panic::{self, AssertUnwindSafe, PanicHookInfo},
+    process,
+    sync::{Arc, Condvar, Mutex, Once},
+    thread,
+    time::Duration,
+};
+
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    answers::Check,
+    protocol::{self, PartResult, Status},
+    runner::TIMEOUT_ENV,
+};
+
+/// Exit code of a solution that was stopped by the [`Watchdog`].
+const TIMEOUT_EXIT_CODE: i32 = 124;
+
+thread_local! {
+    static IS_CATCHING: Cell<bool> = const { Cell::new(false) };
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `func` and returns the panic message and location if it panics.
+/// The message is not printed, so the runner can report it in line with the other parts.
+pub fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    static HOOK: Once = Once::new();
+
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if IS_CATCHING.get() {
+                LAST_PANIC.set(Some(format_panic(info)));
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+
+    let was_catching = IS_CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    IS_CATCHING.set(was_catching);
+
+    result.map_err(|_| {
+        LAST_PANIC
+            .take()
+            .unwrap_or_else(|| "panicked without a message".into())
+    })
+}
+
+fn format_panic(info: &PanicHookInfo) -> String {
+    let message = info.payload_as_str().unwrap_or("Box<dyn Any>");
+    match info.location() {
+        Some(location) => format!("{message} at {location}"),
+        None => message.into(),
+    }
+}
+
+/// The wall-clock time a part may take, read from [`TIMEOUT_ENV`] in seconds.
+pub fn timeout() -> Option<Duration> {
+    env::var(TIMEOUT_ENV)
+        .ok()?
+        .parse::<f64>()
+        .ok()
+        .filter(|secs| *secs > 0.0)
+        .map(Duration::from_secs_f64)
+}
+
+/// Ends the process if it is not dropped within the timeout.
+/// Before exiting, it reports a [`Status::Timeout`] record for the part it watches.
+pub struct Watchdog {
+    is_done: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl Watchdog {
+    pub fn start(part: u8, label: &str) -> Option<Self> {
+        let timeout = timeout()?;
+        let is_done = Arc::new((Mutex::new(false), Condvar::new()));
+
+        let label = label.to_string();
+        let watched = Arc::clone(&is_done);
+
+        thread::spawn(move || {
+            let (lock, condvar) = &*watched;
+            let guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+            let (is_done, _) = condvar
+                .wait_timeout_while(guard, timeout, |is_done| !*is_done)
+                .unwrap_or_else(|e| e.into_inner());
+
+            if *is_done {
+                return;
+            }
+
+            let message = format!("exceeded the timeout of {timeout:?}");
+            print!("\r");
+            println!(
+                "{label}: {ANSI_BOLD}timeout{ANSI_RESET} {ANSI_ITALIC}({message}){ANSI_RESET}"
+            );
+            let _ = stdout().flush();
+
+            protocol::emit(&PartResult {
+                part,
+                status: Status::Timeout,
+                answer: None,
+                #[allow(clippy::cast_possible_truncation)]
+                nanos: timeout.as_nanos() as u64,
+                samples: 0,
+                stats: None,
+                check: Check::Unknown,
+                message: Some(message),
+                alloc: None,
+            });
+
+            process::exit(TIMEOUT_EXIT_CODE);
+        });
+
+        Some(Self { is_done })
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        let (lock, condvar) = &*self.is_done;
+        *lock.lock().unwrap_or_else(|e| e.into_inner()) = true;
+        condvar.notify_one();
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::catch_panic;
+
+    #[test]
+    fn catches_panics_with_location() {
+        let result = catch_panic(|| -> u8 { panic!("boom") });
+        let message = result.unwrap_err();
+        assert!(message.starts_with("boom at src/template/guard.rs:"));
+    }
+
+    #[test]
+    fn passes_through_results() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+    }
+}
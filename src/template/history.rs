@@ -0,0 +1,477 @@
+/// Benchmark history of a year, stored as one JSON line per `cargo time --store` in `data/{year}/timings-history.jsonl`.
+/// Variants of the benchmarks have their own history, e.g. `data/{year}/timings-history-debug.jsonl`.
+///
+/// Every entry is tagged with the git commit, the rustc version and a fingerprint of the machine,
+/// so timings of a later run can be compared with the run of a given commit.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    num::NonZero,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    Day, Year,
+    hash::fnv1a,
+    protocol::PARSE_PART,
+    stats::{Stats, format_nanos},
+    timings::{Timing, Timings, variant_file_name},
+};
+
+static HISTORY_FILE_STEM: &str = "timings-history";
+
+/// Timings of one `cargo time --store` run.
+#[derive(Clone, Debug)]
+pub struct Entry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub commit: Option<String>,
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+    pub rustc: Option<String>,
+    pub machine: String,
+    pub timings: Timings,
+}
+
+impl Entry {
+    /// Tags `timings` with the current commit, compiler and machine.
+    pub fn new(timings: Timings) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            commit: git(&["rev-parse", "HEAD"]),
+            dirty: git(&["status", "--porcelain", "--untracked-files=no"])
+                .is_some_and(|status| !status.is_empty()),
+            rustc: option_env!("AOC_RUSTC_VERSION").map(Into::into),
+            machine: machine_fingerprint(),
+            timings,
+        }
+    }
+
+    fn label(&self) -> String {
+        let commit = self
+            .commit
+            .as_deref()
+            .map_or("unknown commit", |c| &c[..c.len().min(7)]);
+        if self.dirty {
+            format!("{commit} (dirty)")
+        } else {
+            commit.into()
+        }
+    }
+}
+
+/// Entry of the history to compare new timings with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Baseline {
+    /// The most recent entry recorded on this machine.
+    Latest,
+    /// The most recent entry of a commit, e.g. `HEAD~1` or a commit hash.
+    Commit(String),
+}
+
+fn history_path(year: Year, variant: Option<&str>) -> PathBuf {
+    Path::new(&year.data_dir()).join(variant_file_name(HISTORY_FILE_STEM, variant, "jsonl"))
+}
+
+pub fn append(year: Year, variant: Option<&str>, entry: &Entry) -> Result<(), io::Error> {
+    fs::create_dir_all(year.data_dir())?;
+    let line = JsonValue::from(entry)
+        .stringify()
+        .expect("history entry is serializable");
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(history_path(year, variant))?;
+    writeln!(file, "{line}")
+}
+
+/// Reads all entries of the history of `year`, oldest first. A missing file yields no entries.
+pub fn read(year: Year, variant: Option<&str>) -> Vec<Entry> {
+    fs::read_to_string(history_path(year, variant))
+        .map(|contents| parse_entries(&contents))
+        .unwrap_or_default()
+}
+
+fn parse_entries(contents: &str) -> Vec<Entry> {
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| match Entry::try_from(l) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                eprintln!("Could not parse history entry: {e}");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Finds the entry to compare with, preferring entries of the current machine.
+pub fn find_baseline<'a>(entries: &'a [Entry], baseline: &Baseline) -> Option<&'a Entry> {
+    let machine = machine_fingerprint();
+
+    match baseline {
+        Baseline::Latest => entries
+            .iter()
+            .rev()
+            .find(|e| e.machine == machine)
+            .or_else(|| entries.last()),
+        Baseline::Commit(reference) => {
+            let commit = git(&["rev-parse", "--verify", &format!("{reference}^{{commit}}")])
+                .unwrap_or_else(|| reference.clone());
+            let matching = entries
+                .iter()
+                .rev()
+                .filter(|e| e.commit.as_ref().is_some_and(|c| c.starts_with(&commit)));
+            matching
+                .clone()
+                .find(|e| e.machine == machine)
+                .or_else(|| matching.clone().next())
+        }
+    }
+}
+
+/// Change of the mean duration of one step between two runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    /// The part, or [`PARSE_PART`] for the parse step.
+    pub part: u8,
+    pub before: Stats,
+    pub after: Stats,
+}
+
+impl Delta {
+    /// Relative change of the mean, e.g. `0.1` for a slowdown of 10%.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn change(&self) -> f64 {
+        if self.before.mean == 0 {
+            return 0.0;
+        }
+        (self.after.mean as f64 - self.before.mean as f64) / self.before.mean as f64
+    }
+
+    /// Whether the confidence intervals of both runs do not overlap.
+    pub fn is_significant(&self) -> bool {
+        self.after.ci_low > self.before.ci_high || self.after.ci_high < self.before.ci_low
+    }
+
+    /// A significant slowdown of more than `threshold`, e.g. `0.05` for 5%.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.is_significant() && self.change() > threshold
+    }
+
+    pub fn is_improvement(&self, threshold: f64) -> bool {
+        self.is_significant() && self.change() < -threshold
+    }
+}
+
+/// Pairs the benched steps of `after` with the same steps in `before`.
+pub fn compare(before: &Timings, after: &Timings) -> Vec<Delta> {
+    let steps = |t: &Timing| {
+        [
+            (PARSE_PART, t.parse_stats),
+            (1, t.part_1_stats),
+            (2, t.part_2_stats),
+        ]
+    };
+
+    let mut deltas = vec![];
+
+    for timing in &after.data {
+        let Some(previous) = before.data.iter().find(|t| t.day == timing.day) else {
+            continue;
+        };
+
+        for ((part, after), (_, before)) in steps(timing).into_iter().zip(steps(previous)) {
+            if let (Some(before), Some(after)) = (before, after) {
+                deltas.push(Delta {
+                    day: timing.day,
+                    part,
+                    before,
+                    after,
+                });
+            }
+        }
+    }
+
+    deltas
+}
+
+/// Prints the deltas to `baseline` and returns the number of regressions.
+pub fn print_comparison(baseline: &Entry, deltas: &[Delta], threshold: f64) -> usize {
+    println!(
+        "\nCompared with {}, {}:",
+        baseline.label(),
+        baseline.rustc.as_deref().unwrap_or("unknown rustc")
+    );
+
+    if baseline.machine != machine_fingerprint() {
+        println!("Note: the baseline was recorded on a different machine.");
+    }
+
+    if deltas.is_empty() {
+        println!("No benched parts in common.");
+        return 0;
+    }
+
+    let mut regressions = 0;
+
+    for delta in deltas {
+        let part = if delta.part == PARSE_PART {
+            "parse".into()
+        } else {
+            format!("part {}", delta.part)
+        };
+
+        let verdict = if delta.is_regression(threshold) {
+            regressions += 1;
+            " ✘ regression"
+        } else if delta.is_improvement(threshold) {
+            " ✔ improvement"
+        } else {
+            ""
+        };
+
+        println!(
+            "Day {} {part}: {} → {} ({:+.1}%){verdict}",
+            delta.day,
+            format_nanos(delta.before.mean),
+            format_nanos(delta.after.mean),
+            delta.change() * 100.0
+        );
+    }
+
+    regressions
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Hash of the operating system, architecture, cpu model, number of cores and host name.
+fn machine_fingerprint() -> String {
+    let cpu = fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|info| {
+            info.lines()
+                .find(|l| l.starts_with("model name"))
+                .and_then(|l| l.split(':').nth(1))
+                .map(|model| model.trim().to_string())
+        })
+        .unwrap_or_default();
+
+    let host = fs::read_to_string("/etc/hostname")
+        .ok()
+        .or_else(|| env::var("HOSTNAME").ok())
+        .or_else(|| env::var("COMPUTERNAME").ok())
+        .unwrap_or_default();
+
+    let cores = thread::available_parallelism().map_or(0, NonZero::get);
+
+    let description = format!(
+        "{}|{}|{cpu}|{cores}|{}",
+        env::consts::OS,
+        env::consts::ARCH,
+        host.trim()
+    );
+    format!("{:016x}", fnv1a(description.as_bytes()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Entry> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Entry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let string = |x: &Option<String>| match x {
+            Some(x) => JsonValue::String(x.clone()),
+            None => JsonValue::Null,
+        };
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("commit".into(), string(&value.commit));
+        map.insert("dirty".into(), JsonValue::Boolean(value.dirty));
+        map.insert("rustc".into(), string(&value.rustc));
+        map.insert("machine".into(), JsonValue::String(value.machine.clone()));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for Entry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected history entry to be a JSON object.")?;
+
+        let string = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected entry.data to be an array.")?;
+
+        Ok(Entry {
+            timestamp: json
+                .get("timestamp")
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or("expected entry.timestamp to be a number.")?,
+            commit: string("commit"),
+            dirty: json
+                .get("dirty")
+                .and_then(|v| v.get::<bool>())
+                .copied()
+                .unwrap_or_default(),
+            rustc: string("rustc"),
+            machine: string("machine").ok_or("expected entry.machine to be a string.")?,
+            timings: Timings {
+                data: data
+                    .iter()
+                    .map(Timing::try_from)
+                    .collect::<Result<_, _>>()?,
+            },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Baseline, Entry, compare, find_baseline, parse_entries};
+    use crate::{
+        day,
+        template::{
+            stats::Stats,
+            timings::{Measurement, Timing, Timings},
+        },
+    };
+    use tinyjson::JsonValue;
+
+    fn stats(mean: u64, margin: u64) -> Stats {
+        Stats {
+            mean,
+            ci_low: mean - margin,
+            ci_high: mean + margin,
+            samples: 100,
+            ..Stats::default()
+        }
+    }
+
+    fn get_mock_timings(part_1: Stats, part_2: Option<Stats>) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                parse: None,
+                parse_stats: None,
+                part_1: Some(Measurement {
+                    nanos: part_1.mean,
+                    samples: part_1.samples,
+                }),
+                part_2: part_2.map(|stats| Measurement {
+                    nanos: stats.mean,
+                    samples: stats.samples,
+                }),
+                part_1_stats: Some(part_1),
+                part_2_stats: part_2,
+                parse_failure: None,
+                part_1_failure: None,
+                part_2_failure: None,
+                parse_alloc: None,
+                part_1_alloc: None,
+                part_2_alloc: None,
+            }],
+        }
+    }
+
+    fn get_mock_entry(commit: &str, machine: &str) -> Entry {
+        Entry {
+            timestamp: 1_760_000_000,
+            commit: Some(commit.into()),
+            dirty: false,
+            rustc: Some("rustc 1.95.0".into()),
+            machine: machine.into(),
+            timings: get_mock_timings(stats(1_000_000, 10_000), None),
+        }
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let entry = get_mock_entry("abc1234", "0123456789abcdef");
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        let parsed = parse_entries(&format!("{line}\n\n{line}\n"));
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].commit, entry.commit);
+        assert_eq!(parsed[0].machine, entry.machine);
+        assert_eq!(
+            parsed[0].timings.data[0].part_1_stats,
+            entry.timings.data[0].part_1_stats
+        );
+    }
+
+    #[test]
+    fn finds_baselines_by_commit() {
+        let entries = [
+            get_mock_entry("aaaaaaa111", "other"),
+            get_mock_entry("bbbbbbb222", "other"),
+        ];
+        let found = find_baseline(&entries, &Baseline::Commit("aaaaaaa".into()));
+        assert_eq!(found.unwrap().commit.as_deref(), Some("aaaaaaa111"));
+        let latest = find_baseline(&entries, &Baseline::Latest);
+        assert_eq!(latest.unwrap().commit.as_deref(), Some("bbbbbbb222"));
+        assert!(find_baseline(&[], &Baseline::Latest).is_none());
+    }
+
+    #[test]
+    fn flags_significant_regressions() {
+        let before = get_mock_timings(stats(1_000_000, 10_000), Some(stats(1_000_000, 10_000)));
+        let after = get_mock_timings(stats(1_200_000, 10_000), Some(stats(1_010_000, 10_000)));
+        let deltas = compare(&before, &after);
+        assert_eq!(deltas.len(), 2);
+
+        // 20% slower with disjunct confidence intervals.
+        assert!(deltas[0].is_regression(0.05));
+        assert!((deltas[0].change() - 0.2).abs() < 1e-9);
+        // 1% slower, within the noise.
+        assert!(!deltas[1].is_significant());
+        assert!(!deltas[1].is_regression(0.05));
+    }
+
+    #[test]
+    fn flags_improvements() {
+        let before = get_mock_timings(stats(1_000_000, 10_000), None);
+        let after = get_mock_timings(stats(500_000, 10_000), None);
+        let deltas = compare(&before, &after);
+        assert_eq!(deltas.len(), 1);
+        assert!(deltas[0].is_improvement(0.05));
+        assert!(!deltas[0].is_regression(0.05));
+    }
+}
@@ -0,0 +1,467 @@
+/// Minimal HTML handling for pages of the puzzle website.
+/// This is not a general purpose HTML parser, it only handles what is needed to convert puzzle descriptions
+/// to markdown and to extract text from submission responses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Node {
+    Element {
+        tag: String,
+        attrs: Vec<(String, String)>,
+        children: Vec<Node>,
+    },
+    Text(String),
+}
+
+/// An element that has been opened but not closed yet: tag, attributes and children.
+type OpenElement = (String, Vec<(String, String)>, Vec<Node>);
+
+const VOID_ELEMENTS: [&str; 6] = ["br", "hr", "img", "input", "link", "meta"];
+
+impl Node {
+    fn attr(&self, name: &str) -> Option<&str> {
+        match self {
+            Node::Element { attrs, .. } => attrs
+                .iter()
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v.as_str()),
+            Node::Text(_) => None,
+        }
+    }
+
+    fn children(&self) -> &[Node] {
+        match self {
+            Node::Element { children, .. } => children,
+            Node::Text(_) => &[],
+        }
+    }
+
+    fn has_class(&self, class: &str) -> bool {
+        self.attr("class")
+            .is_some_and(|c| c.split_whitespace().any(|x| x == class))
+    }
+}
+
+/// Parses an HTML document into a list of top-level nodes.
+pub fn parse(html: &str) -> Vec<Node> {
+    // stack of open elements, the bottom entry collects top-level nodes.
+    let mut stack: Vec<OpenElement> = vec![(String::new(), vec![], vec![])];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+
+        push_text(&mut stack, &rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        let Some(end) = rest.find('>') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+
+        let tag_str = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if tag_str.starts_with('!') || tag_str.starts_with('?') {
+            continue;
+        }
+
+        if let Some(name) = tag_str.strip_prefix('/') {
+            let name = name.trim().to_ascii_lowercase();
+            // close the matching element, implicitly closing unclosed children.
+            if stack.iter().skip(1).any(|(tag, _, _)| *tag == name) {
+                while let Some((tag, attrs, children)) = stack.pop() {
+                    let is_match = tag == name;
+                    append(
+                        &mut stack,
+                        Node::Element {
+                            tag,
+                            attrs,
+                            children,
+                        },
+                    );
+                    if is_match {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+
+        let is_self_closing = tag_str.ends_with('/');
+        let tag_str = tag_str.trim_end_matches('/');
+        let (name, attrs) = parse_tag(tag_str);
+
+        if name == "script" || name == "style" {
+            let close = format!("</{name}>");
+            rest = rest
+                .find(&close)
+                .map_or("", |end| &rest[end + close.len()..]);
+            continue;
+        }
+
+        if is_self_closing || VOID_ELEMENTS.contains(&name.as_str()) {
+            append(
+                &mut stack,
+                Node::Element {
+                    tag: name,
+                    attrs,
+                    children: vec![],
+                },
+            );
+        } else {
+            stack.push((name, attrs, vec![]));
+        }
+    }
+
+    while stack.len() > 1 {
+        let (tag, attrs, children) = stack.pop().unwrap();
+        append(
+            &mut stack,
+            Node::Element {
+                tag,
+                attrs,
+                children,
+            },
+        );
+    }
+
+    stack
+        .pop()
+        .map(|(_, _, children)| children)
+        .unwrap_or_default()
+}
+
+fn append(stack: &mut [OpenElement], node: Node) {
+    if let Some((_, _, children)) = stack.last_mut() {
+        children.push(node);
+    }
+}
+
+fn push_text(stack: &mut [OpenElement], text: &str) {
+    if !text.is_empty() {
+        append(stack, Node::Text(decode_entities(text)));
+    }
+}
+
+fn parse_tag(s: &str) -> (String, Vec<(String, String)>) {
+    let s = s.trim();
+    let name_end = s.find(char::is_whitespace).unwrap_or(s.len());
+    let name = s[..name_end].to_ascii_lowercase();
+
+    let mut attrs = vec![];
+    let mut rest = s[name_end..].trim_start();
+
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = rest[..key_end].to_ascii_lowercase();
+        rest = rest[key_end..].trim_start();
+
+        let value = if let Some(after_eq) = rest.strip_prefix('=') {
+            let after_eq = after_eq.trim_start();
+            let (value, remainder) = match after_eq.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let inner = &after_eq[1..];
+                    let end = inner.find(quote).unwrap_or(inner.len());
+                    (&inner[..end], inner.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = after_eq.find(char::is_whitespace).unwrap_or(after_eq.len());
+                    (&after_eq[..end], &after_eq[end..])
+                }
+            };
+            rest = remainder.trim_start();
+            decode_entities(value)
+        } else {
+            String::new()
+        };
+
+        if !key.is_empty() {
+            attrs.push((key, value));
+        }
+    }
+
+    (name, attrs)
+}
+
+fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .and_then(|x| u32::from_str_radix(x, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|x| x.parse().ok()))
+                    .and_then(char::from_u32),
+            }?;
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns all elements named `tag` that have `class` set (if given), in document order.
+pub fn find_all<'a>(nodes: &'a [Node], tag: &str, class: Option<&str>) -> Vec<&'a Node> {
+    let mut found = vec![];
+    for node in nodes {
+        if let Node::Element { tag: t, .. } = node
+            && t == tag
+            && class.is_none_or(|c| node.has_class(c))
+        {
+            found.push(node);
+        }
+        found.extend(find_all(node.children(), tag, class));
+    }
+    found
+}
+
+/// Returns the attribute `name` of every element in `nodes` that has it, in document order.
+pub fn find_attrs<'a>(nodes: &'a [Node], name: &str) -> Vec<(&'a Node, &'a str)> {
+    let mut found = vec![];
+    for node in nodes {
+        if let Some(value) = node.attr(name) {
+            found.push((node, value));
+        }
+        found.extend(find_attrs(node.children(), name));
+    }
+    found
+}
+
+/// Extracts the text content of `nodes`, collapsing whitespace.
+pub fn to_text(nodes: &[Node]) -> String {
+    let mut out = String::new();
+    collect_text(nodes, &mut out);
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn collect_text(nodes: &[Node], out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Element { children, .. } => collect_text(children, out),
+        }
+    }
+}
+
+/// Renders `nodes` as markdown.
+pub fn to_markdown(nodes: &[Node]) -> String {
+    let mut out = String::new();
+    render_markdown(nodes, &mut out);
+    // collapse the blank lines left behind by nested block elements.
+    let mut md = out
+        .split('\n')
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    while md.contains("\n\n\n") {
+        md = md.replace("\n\n\n", "\n\n");
+    }
+    md.trim().to_string() + "\n"
+}
+
+fn render_markdown(nodes: &[Node], out: &mut String) {
+    for node in nodes {
+        let Node::Element { tag, children, .. } = node else {
+            if let Node::Text(text) = node {
+                out.push_str(text);
+            }
+            continue;
+        };
+
+        match tag.as_str() {
+            "h1" | "h2" | "h3" => {
+                let level = tag[1..].parse().unwrap_or(2);
+                out.push_str(&format!("\n{} ", "#".repeat(level)));
+                render_markdown(children, out);
+                out.push_str("\n\n");
+            }
+            "p" => {
+                out.push('\n');
+                render_markdown(children, out);
+                out.push_str("\n\n");
+            }
+            "pre" => {
+                let mut code = String::new();
+                collect_text(children, &mut code);
+                out.push_str("\n```\n");
+                out.push_str(code.trim_end_matches('\n'));
+                out.push_str("\n```\n\n");
+            }
+            "code" => {
+                let mut code = String::new();
+                collect_text(children, &mut code);
+                // answers are usually marked as `<code><em>42</em></code>`, keep the emphasis.
+                let is_emphasized = !children.is_empty()
+                    && children
+                        .iter()
+                        .all(|c| matches!(c, Node::Element { tag, .. } if tag == "em"));
+                if is_emphasized {
+                    out.push_str(&format!("*`{code}`*"));
+                } else {
+                    out.push_str(&format!("`{code}`"));
+                }
+            }
+            "em" | "i" => {
+                out.push('*');
+                render_markdown(children, out);
+                out.push('*');
+            }
+            "strong" | "b" => {
+                out.push_str("**");
+                render_markdown(children, out);
+                out.push_str("**");
+            }
+            "a" => {
+                out.push('[');
+                render_markdown(children, out);
+                out.push_str(&format!("]({})", node.attr("href").unwrap_or_default()));
+            }
+            "ul" | "ol" => {
+                // skip the whitespace between list items.
+                let items: Vec<Node> = children
+                    .iter()
+                    .filter(|c| matches!(c, Node::Element { .. }))
+                    .cloned()
+                    .collect();
+                out.push('\n');
+                render_markdown(&items, out);
+                out.push('\n');
+            }
+            "li" => {
+                out.push_str("\n- ");
+                render_markdown(children, out);
+            }
+            "br" => out.push('\n'),
+            _ => render_markdown(children, out),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Node, find_all, parse, to_markdown, to_text};
+
+    #[test]
+    fn parses_nested_elements() {
+        let nodes = parse(r#"<div class="a b"><p>x<br>y</p></div>"#);
+        assert_eq!(
+            nodes,
+            vec![Node::Element {
+                tag: "div".into(),
+                attrs: vec![("class".into(), "a b".into())],
+                children: vec![Node::Element {
+                    tag: "p".into(),
+                    attrs: vec![],
+                    children: vec![
+                        Node::Text("x".into()),
+                        Node::Element {
+                            tag: "br".into(),
+                            attrs: vec![],
+                            children: vec![]
+                        },
+                        Node::Text("y".into()),
+                    ]
+                }]
+            }]
+        );
+    }
+
+    #[test]
+    fn skips_scripts_and_comments() {
+        let nodes = parse("<!-- <p>no</p> --><script>let x = '<p>';</script><p>yes</p>");
+        assert_eq!(to_text(&nodes), "yes");
+    }
+
+    #[test]
+    fn decodes_entities() {
+        let nodes = parse("<p>a &lt;b&gt; &amp; &#39;c&#x27; &unknown</p>");
+        assert_eq!(to_text(&nodes), "a <b> & 'c' &unknown");
+    }
+
+    #[test]
+    fn finds_elements_by_class() {
+        let nodes = parse(
+            r#"<main><article class="day-desc"><p>1</p></article><article><p>2</p></article></main>"#,
+        );
+        assert_eq!(find_all(&nodes, "article", Some("day-desc")).len(), 1);
+        assert_eq!(find_all(&nodes, "article", None).len(), 2);
+    }
+
+    #[test]
+    fn converts_puzzle_descriptions() {
+        let html = r#"<article class="day-desc"><h2>--- Day 1: Test ---</h2>
+<p>For example, see <a href="/2025/about">this</a>:</p>
+<pre><code>1 &lt; 2
+3
+</code></pre>
+<ul>
+<li>One <code>x</code></li>
+<li>Two</li>
+</ul>
+<p>The answer is <code><em>42</em></code>, <em>not</em> <em><code>43</code></em>.</p>
+</article>"#;
+        let nodes = parse(html);
+        assert_eq!(
+            to_markdown(&nodes),
+            [
+                "## --- Day 1: Test ---",
+                "",
+                "For example, see [this](/2025/about):",
+                "",
+                "```",
+                "1 < 2",
+                "3",
+                "```",
+                "",
+                "- One `x`",
+                "- Two",
+                "",
+                "The answer is *`42`*, *not* *`43`*.",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
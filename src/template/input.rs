@@ -0,0 +1,211 @@
+/// Selects the input a solution binary runs on. By default, that is the puzzle input in `data/{year}/inputs/{day}.txt`.
+/// `--input <path>` reads another file, e.g. a teammate's input, `--input -` reads standard input and
+/// `--example [N]` reads an example file. `--examples` runs every case of the example manifest, see [`example_cases`].
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::PathBuf,
+    process,
+};
+
+use crate::template::{PuzzleId, Year, answers::Answers, example_cases, runner};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// The puzzle input, `data/{year}/inputs/{day}.txt`.
+    Puzzle,
+    /// A file passed with `--input <path>`.
+    File(PathBuf),
+    /// Standard input, selected with `--input -`.
+    Stdin,
+    /// An example file, `data/{year}/examples/{day}.txt` or e.g. `{day}-2.txt` with `--example 2`.
+    /// Like [`crate::template::read_file_part`], `--example 1` is the first example, `{day}.txt`.
+    Example(Option<u8>),
+}
+
+impl Source {
+    /// Parses `--input <path>` and `--example [N]` from the arguments of a solution binary.
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let position = |name: &str| args.iter().position(|arg| arg == name);
+
+        match (position("--input"), position("--example")) {
+            (Some(_), Some(_)) => Err("pass either `--input` or `--example`, not both.".into()),
+            (Some(index), None) => match args.get(index + 1).map(String::as_str) {
+                Some("-") => Ok(Source::Stdin),
+                Some(path) if !path.starts_with('-') => Ok(Source::File(path.into())),
+                _ => Err("`--input` expects a path, or `-` to read from standard input.".into()),
+            },
+            (None, Some(index)) => Ok(Source::Example(
+                args.get(index + 1).and_then(|arg| arg.parse().ok()),
+            )),
+            (None, None) => Ok(Source::Puzzle),
+        }
+    }
+
+    /// Arguments that select this input, passed on to the solution binary by `cargo solve`.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Source::Puzzle => vec![],
+            Source::File(path) => vec!["--input".into(), path.display().to_string()],
+            Source::Stdin => vec!["--input".into(), "-".into()],
+            Source::Example(None) => vec!["--example".into()],
+            Source::Example(Some(n)) => vec!["--example".into(), n.to_string()],
+        }
+    }
+
+    fn path(&self, puzzle: PuzzleId) -> Option<PathBuf> {
+        match self {
+            Source::Puzzle => Some(puzzle.data_path("inputs", "txt")),
+            Source::File(path) => Some(path.clone()),
+            Source::Stdin => None,
+            Source::Example(None | Some(1)) => Some(puzzle.data_path("examples", "txt")),
+            Source::Example(Some(n)) => Some(
+                PathBuf::from(puzzle.year.data_dir())
+                    .join("examples")
+                    .join(format!("{}-{n}.txt", puzzle.day)),
+            ),
+        }
+    }
+
+    /// Reads the input, with an error message that names the expected path.
+    pub fn read(&self, puzzle: PuzzleId) -> Result<String, String> {
+        let Some(path) = self.path(puzzle) else {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("Could not read from standard input: {e}"))?;
+            return Ok(input);
+        };
+
+        fs::read_to_string(&path).map_err(|e| match (self, e.kind()) {
+            (Source::Puzzle, io::ErrorKind::NotFound) => format!(
+                "Could not find the input of {puzzle} at \"{}\".\nRun `cargo download {}{}` to download it, or pass `--input <path>` to use another file.",
+                path.display(),
+                puzzle.day,
+                get_year_arg(puzzle.year)
+            ),
+            _ => format!("Could not read \"{}\": {e}", path.display()),
+        })
+    }
+}
+
+fn get_year_arg(year: Year) -> String {
+    if Year::from_env() == Some(year) {
+        String::new()
+    } else {
+        format!(" --year {year}")
+    }
+}
+
+/// Runs a solution on the input selected by the arguments of its binary, used by the `main` of [`crate::solution`].
+pub fn run(puzzle: PuzzleId, run: fn(&str)) {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if args.iter().any(|arg| arg == "--examples") {
+        example_cases::run(puzzle, run);
+        return;
+    }
+
+    let source = Source::from_args(&args).unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    });
+
+    let input = source.read(puzzle).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    });
+
+    match source {
+        Source::Puzzle => run(&input),
+        // examples have no expected answers here, but solutions still receive their example parameters.
+        Source::Example(_) => runner::run_example(Answers::default(), || run(&input)),
+        Source::File(_) | Source::Stdin => runner::run_other_input(|| run(&input)),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::Source;
+    use crate::{
+        day,
+        template::{PuzzleId, Year},
+        year,
+    };
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_sources() {
+        assert_eq!(Source::from_args(&args(&["--release"])), Ok(Source::Puzzle));
+        assert_eq!(
+            Source::from_args(&args(&["--input", "other.txt"])),
+            Ok(Source::File("other.txt".into()))
+        );
+        assert_eq!(
+            Source::from_args(&args(&["--input", "-"])),
+            Ok(Source::Stdin)
+        );
+        assert_eq!(
+            Source::from_args(&args(&["--example"])),
+            Ok(Source::Example(None))
+        );
+        assert_eq!(
+            Source::from_args(&args(&["--example", "2", "--time"])),
+            Ok(Source::Example(Some(2)))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_sources() {
+        assert!(Source::from_args(&args(&["--input"])).is_err());
+        assert!(Source::from_args(&args(&["--input", "--time"])).is_err());
+        assert!(Source::from_args(&args(&["--input", "a.txt", "--example"])).is_err());
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        for source in [
+            Source::Puzzle,
+            Source::File("other.txt".into()),
+            Source::Stdin,
+            Source::Example(None),
+            Source::Example(Some(2)),
+        ] {
+            assert_eq!(Source::from_args(&source.to_args()), Ok(source));
+        }
+    }
+
+    #[test]
+    fn reads_the_first_example_without_suffix() {
+        let puzzle = PuzzleId::new(year!(2025), day!(8));
+        assert_eq!(
+            Source::Example(Some(1)).path(puzzle),
+            Source::Example(None).path(puzzle)
+        );
+        assert_eq!(
+            Source::Example(Some(2)).path(puzzle),
+            Some(PathBuf::from("data/2025/examples/08-2.txt"))
+        );
+    }
+
+    #[test]
+    fn explains_missing_inputs() {
+        let year = Year::from_env().unwrap_or(year!(2025));
+        let puzzle = PuzzleId::new(year, day!(25));
+        let error = Source::File("does-not-exist.txt".into())
+            .read(puzzle)
+            .unwrap_err();
+        assert!(error.contains("\"does-not-exist.txt\""));
+
+        let error = Source::Puzzle.read(puzzle).unwrap_err();
+        assert!(error.contains(&format!("data/{year}/inputs/25.txt")));
+        assert!(error.contains("cargo download 25"));
+    }
+}
@@ -1,44 +1,90 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod cache;
+pub mod client;
 pub mod commands;
+pub mod example_cases;
+pub mod export;
+pub mod history;
+pub mod input;
+pub mod rate_limit;
+pub mod registry;
 pub mod runner;
 
+pub use answer::{Answer, ToAnswer};
+pub use calendar::*;
 pub use day::*;
+pub use puzzle::*;
+pub use year::*;
 
+mod alloc_stats;
+mod answer;
+mod answers;
+mod calendar;
+mod chart;
 mod day;
+mod examples;
+mod guard;
+mod hash;
+mod html;
+mod progress;
+mod protocol;
+mod puzzle;
 mod readme_benchmarks;
+mod readme_stars;
 mod run_multi;
+mod stats;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Constants of a solution that differ between the examples and the real input,
+/// e.g. the number of steps to simulate. See the `params` option of [`solution!`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Params<T> {
+    pub example: T,
+    pub real: T,
+}
+
+/// Helper function that reads a text file of a puzzle to a string, e.g. `data/2025/inputs/01.txt`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(puzzle.year.data_dir())
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
+/// The year is read from the name of the binary, e.g. `2025-01`.
+/// The generated `__run` function is what the in-process runner of `cargo all` and `cargo time` calls.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Pass `parse = <function>` to parse the input once before running the parts.
+/// The parse step is timed on its own and both parts receive a reference to its output.
+///
+/// Pass `params = <constant>` with a [`Params`] constant to give both parts a second argument,
+/// a reference to its `real` value. Examples and tests use its `example` value instead, see `cargo scaffold --params`.
+///
+/// Cases listed in `data/{year}/examples/{day}.json` become tests of the solution, see [`example_cases`].
+/// The binary runs on the puzzle input unless its arguments select another one, see [`input`].
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +96,99 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr, 1) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1]);
+    };
+    ($day:expr, parse = $parse:expr, 2) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_two, 2]);
+    };
+    ($day:expr, params = $params:expr) => {
+        $crate::solution!(@impl $day, params = $params, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, params = $params:expr, 1) => {
+        $crate::solution!(@impl $day, params = $params, [part_one, 1]);
+    };
+    ($day:expr, params = $params:expr, 2) => {
+        $crate::solution!(@impl $day, params = $params, [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr, params = $params:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, params = $params, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr, params = $params:expr, 1) => {
+        $crate::solution!(@impl $day, parse = $parse, params = $params, [part_one, 1]);
+    };
+    ($day:expr, parse = $parse:expr, params = $params:expr, 2) => {
+        $crate::solution!(@impl $day, parse = $parse, params = $params, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        /// Runs every part of the solution, see [`advent_of_code::template::registry`].
+        #[doc(hidden)]
+        pub fn __run(input: &str) {
+            use $crate::template::runner::*;
+            $( run_part($func, input, PUZZLE, $part); )*
+        }
+    };
+    (@impl $day:expr, parse = $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        /// Runs every part of the solution, see [`advent_of_code::template::registry`].
+        #[doc(hidden)]
+        pub fn __run(input: &str) {
+            use $crate::template::runner::*;
+            let parsed = run_parse($parse, input);
+            $( run_part($func, &parsed, PUZZLE, $part); )*
+        }
+    };
+    (@impl $day:expr, params = $params:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        /// Runs every part of the solution, see [`advent_of_code::template::registry`].
+        #[doc(hidden)]
+        pub fn __run(input: &str) {
+            use $crate::template::runner::*;
+            let params = if is_example() { &$params.example } else { &$params.real };
+            $( run_part(|input| $func(input, params), input, PUZZLE, $part); )*
+        }
+    };
+    (@impl $day:expr, parse = $parse:expr, params = $params:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        /// Runs every part of the solution, see [`advent_of_code::template::registry`].
+        #[doc(hidden)]
+        pub fn __run(input: &str) {
+            use $crate::template::runner::*;
+            let params = if is_example() { &$params.example } else { &$params.real };
+            let parsed = run_parse($parse, input);
+            $( run_part(|parsed| $func(parsed, params), &parsed, PUZZLE, $part); )*
+        }
+    };
+
+    (@header $day:expr) => {
+        /// The current year.
+        pub const YEAR: $crate::template::Year =
+            $crate::template::Year::from_module_path(module_path!())
+                .expect("invalid binary name, expecting the year first (e.g. `2025-01`)");
         /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        pub const DAY: $crate::template::Day = $crate::day!($day);
+        /// The current puzzle.
+        pub const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::new(YEAR, DAY);
+
+        fn main() {
+            $crate::template::input::run(PUZZLE, __run);
+        }
+
+        // one test per case of the example manifest, see `build.rs`.
+        #[cfg(test)]
+        include!(concat!(env!("OUT_DIR"), "/examples/", env!("CARGO_BIN_NAME"), ".rs"));
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
-
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
-        }
     };
 }
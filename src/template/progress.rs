@@ -0,0 +1,293 @@
+/// Progress of a year, combining the answer registry, the submission log and the stored timings.
+///
+/// A part earns a star once its answer is known to be right: it was accepted into the answer registry,
+/// or the server confirmed it. Like on the site, the second star of the final day is awarded
+/// once every other star of the event has been collected.
+use crate::template::{
+    Calendar, Day, PuzzleId, Year,
+    answers::Answers,
+    submissions::{Outcome, Submission, Submissions},
+    timings::Timings,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartState {
+    /// The answer is known to be right.
+    Solved,
+    /// The solution produced an answer, but it has not been confirmed yet.
+    Answered,
+    /// Every submitted answer was wrong.
+    Attempted,
+    /// The puzzle is unlocked, but the part has not been worked on.
+    Open,
+    /// The puzzle has not been unlocked yet.
+    Locked,
+}
+
+impl PartState {
+    pub fn symbol(self) -> char {
+        match self {
+            PartState::Solved => '★',
+            PartState::Answered => '☆',
+            PartState::Attempted => '✘',
+            PartState::Open => '·',
+            PartState::Locked => ' ',
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayProgress {
+    pub day: Day,
+    pub part_1: PartState,
+    pub part_2: PartState,
+}
+
+impl DayProgress {
+    pub fn stars(&self) -> usize {
+        [self.part_1, self.part_2]
+            .iter()
+            .filter(|&&state| state == PartState::Solved)
+            .count()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Progress {
+    pub year: Year,
+    pub days: Vec<DayProgress>,
+}
+
+impl Progress {
+    /// Reads the progress of every day of `year` at `now`, in seconds since the unix epoch.
+    pub fn read(year: Year, now: u64) -> Self {
+        let timings = Timings::read_from_file(year, None);
+        Self::from_sources(year, now, &timings, |day| {
+            let puzzle = PuzzleId::new(year, day);
+            // files that can't be read only leave their parts unconfirmed here.
+            (
+                Answers::read(puzzle).unwrap_or_default(),
+                Submissions::read(puzzle).unwrap_or_default(),
+            )
+        })
+    }
+
+    fn from_sources(
+        year: Year,
+        now: u64,
+        timings: &Timings,
+        read: impl Fn(Day) -> (Answers, Submissions),
+    ) -> Self {
+        let calendar = Calendar::new(year);
+        let last_day = calendar.last_day();
+
+        let mut days: Vec<DayProgress> = calendar
+            .days()
+            .map(|day| {
+                if !calendar.is_unlocked(day, now) {
+                    return DayProgress {
+                        day,
+                        part_1: PartState::Locked,
+                        part_2: PartState::Locked,
+                    };
+                }
+
+                let (answers, submissions) = read(day);
+                let timing = timings.data.iter().find(|t| t.day == day);
+                let state = |part: u8| {
+                    let is_benched = timing.is_some_and(|t| match part {
+                        1 => t.part_1.is_some(),
+                        _ => t.part_2.is_some(),
+                    });
+                    part_state(answers.get(part), submissions.get(part), is_benched)
+                };
+
+                DayProgress {
+                    day,
+                    part_1: state(1),
+                    part_2: if calendar.parts(day) < 2 {
+                        PartState::Open
+                    } else {
+                        state(2)
+                    },
+                }
+            })
+            .collect();
+
+        // the final day's second star is awarded for collecting all other stars.
+        let other_stars: usize = days.iter().map(DayProgress::stars).sum();
+        if let Some(last) = days.iter_mut().find(|d| d.day == last_day)
+            && last.part_2 != PartState::Locked
+            && last.part_1 == PartState::Solved
+            && other_stars == 2 * days_before(last_day) + 1
+        {
+            last.part_2 = PartState::Solved;
+        }
+
+        Self { year, days }
+    }
+
+    pub fn stars(&self) -> usize {
+        self.days.iter().map(DayProgress::stars).sum()
+    }
+
+    pub fn max_stars(&self) -> usize {
+        2 * self.days.len()
+    }
+}
+
+fn days_before(day: Day) -> usize {
+    usize::from(day.into_inner()) - 1
+}
+
+fn part_state(answer: Option<&str>, submissions: &[Submission], is_benched: bool) -> PartState {
+    let is_confirmed = submissions
+        .iter()
+        .any(|s| matches!(s.outcome, Outcome::Correct | Outcome::AlreadySolved));
+    let is_rejected = submissions.iter().any(|s| {
+        matches!(
+            s.outcome,
+            Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect
+        )
+    });
+
+    if answer.is_some() || is_confirmed {
+        PartState::Solved
+    } else if is_benched {
+        PartState::Answered
+    } else if is_rejected {
+        PartState::Attempted
+    } else {
+        PartState::Open
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartState, Progress};
+    use crate::{
+        day,
+        template::{
+            Calendar, Day,
+            answers::Answers,
+            submissions::{Outcome, Submission, Submissions},
+            timings::{Measurement, Timing, Timings},
+        },
+        year,
+    };
+
+    fn solved(part_1: bool, part_2: bool) -> (Answers, Submissions) {
+        let answer = |solved: bool| solved.then(|| "42".to_string());
+        (
+            Answers {
+                part_1: answer(part_1),
+                part_2: answer(part_2),
+            },
+            Submissions::default(),
+        )
+    }
+
+    fn get_mock_timings(day: Day) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day,
+                parse: None,
+                parse_stats: None,
+                part_1: Some(Measurement {
+                    nanos: 1_000,
+                    samples: 10,
+                }),
+                part_2: None,
+                part_1_stats: None,
+                part_2_stats: None,
+                parse_failure: None,
+                part_1_failure: None,
+                part_2_failure: None,
+                parse_alloc: None,
+                part_1_alloc: None,
+                part_2_alloc: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn combines_answers_submissions_and_timings() {
+        let year = year!(2025);
+        // after day 3 unlocked.
+        let now = Calendar::new(year).unlocks_at(day!(3));
+
+        let progress =
+            Progress::from_sources(year, now, &get_mock_timings(day!(2)), |day| {
+                match day.into_inner() {
+                    1 => solved(true, false),
+                    3 => (
+                        Answers::default(),
+                        Submissions {
+                            part_1: vec![Submission {
+                                answer: "1".into(),
+                                outcome: Outcome::TooLow,
+                                timestamp: 0,
+                            }],
+                            part_2: vec![],
+                        },
+                    ),
+                    _ => solved(false, false),
+                }
+            });
+
+        assert_eq!(progress.days.len(), 12);
+        assert_eq!(progress.days[0].part_1, PartState::Solved);
+        assert_eq!(progress.days[0].part_2, PartState::Open);
+        assert_eq!(progress.days[1].part_1, PartState::Answered);
+        assert_eq!(progress.days[2].part_1, PartState::Attempted);
+        assert_eq!(progress.days[3].part_1, PartState::Locked);
+        assert_eq!(progress.stars(), 1);
+        assert_eq!(progress.max_stars(), 24);
+    }
+
+    #[test]
+    fn counts_confirmed_submissions_as_stars() {
+        let year = year!(2025);
+        let now = Calendar::new(year).unlocks_at(day!(1));
+
+        let progress = Progress::from_sources(year, now, &Timings::default(), |_| {
+            (
+                Answers::default(),
+                Submissions {
+                    part_1: vec![Submission {
+                        answer: "7".into(),
+                        outcome: Outcome::Correct,
+                        timestamp: 0,
+                    }],
+                    part_2: vec![Submission {
+                        answer: "8".into(),
+                        outcome: Outcome::AlreadySolved,
+                        timestamp: 0,
+                    }],
+                },
+            )
+        });
+
+        assert_eq!(progress.days[0].stars(), 2);
+    }
+
+    #[test]
+    fn awards_final_star_for_all_other_stars() {
+        let year = year!(2025);
+        let now = Calendar::new(year).unlocks_at(day!(12));
+
+        let complete =
+            Progress::from_sources(year, now, &Timings::default(), |_| solved(true, true));
+        assert_eq!(complete.days[11].part_2, PartState::Solved);
+        assert_eq!(complete.stars(), 24);
+
+        let incomplete = Progress::from_sources(year, now, &Timings::default(), |day| {
+            solved(true, day != day!(5))
+        });
+        assert_eq!(incomplete.days[11].part_1, PartState::Solved);
+        assert_eq!(incomplete.days[11].part_2, PartState::Open);
+        assert_eq!(incomplete.stars(), 22);
+    }
+}
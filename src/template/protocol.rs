@@ -0,0 +1,438 @@
+/// Machine-readable result records passed from solution binaries to the runner.
+///
+/// When the `AOC_RESULTS_PATH` environment variable is set, every solution part appends one JSON line to that file.
+/// This keeps the runner independent from whatever a solution prints to stdout.
+/// Solutions that run in-process hand their records to a [`collect`] call instead.
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{alloc_stats::AllocStats, answers::Check, stats::Stats};
+
+/// Version of the record format. Bump this when changing the shape of [`PartResult`].
+pub const PROTOCOL_VERSION: u32 = 3;
+
+/// Environment variable that selects the file results are appended to.
+pub const RESULTS_PATH_ENV: &str = "AOC_RESULTS_PATH";
+
+/// Part number used for records of the parse step, see [`crate::template::runner::run_parse`].
+pub const PARSE_PART: u8 = 0;
+
+/// Outcome of running a single solution part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The part returned `Some(answer)`.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The part panicked, see [`PartResult::message`].
+    Panic,
+    /// The part did not finish within the timeout, see [`crate::template::runner::TIMEOUT_ENV`].
+    Timeout,
+}
+
+impl Status {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Panic => "panic",
+            Status::Timeout => "timeout",
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "panic" => Ok(Status::Panic),
+            "timeout" => Ok(Status::Timeout),
+            x => Err(format!("unknown status `{x}`.")),
+        }
+    }
+}
+
+/// A single result record, as emitted by [`crate::template::runner::run_part`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartResult {
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub nanos: u64,
+    pub samples: u64,
+    /// Benchmark statistics, only present when the part was benched.
+    pub stats: Option<Stats>,
+    /// Comparison of the answer with the answer registry.
+    pub check: Check,
+    /// Why the part failed, e.g. the panic message and location.
+    pub message: Option<String>,
+    /// Heap usage of the first execution, only present with the `alloc-stats` feature.
+    pub alloc: Option<AllocStats>,
+}
+
+impl PartResult {
+    /// Serializes the record as a single line of JSON.
+    pub fn to_json_line(&self) -> String {
+        // NOTE: `stringify` escapes newlines, so multi-line answers stay on one line.
+        JsonValue::from(self)
+            .stringify()
+            .expect("result record is serializable")
+    }
+}
+
+thread_local! {
+    static COLLECTED: RefCell<Option<Vec<PartResult>>> = const { RefCell::new(None) };
+}
+
+/// Runs `func` and returns the records it emitted on the current thread, instead of writing them to the results file.
+pub fn collect(func: impl FnOnce()) -> Vec<PartResult> {
+    COLLECTED.with_borrow_mut(|collected| *collected = Some(vec![]));
+    func();
+    COLLECTED
+        .with_borrow_mut(Option::take)
+        .expect("records are collected")
+}
+
+/// Appends `result` to the results file if the runner asked for one.
+pub fn emit(result: &PartResult) {
+    let is_collected = COLLECTED.with_borrow_mut(|collected| match collected {
+        Some(collected) => {
+            collected.push(result.clone());
+            true
+        }
+        None => false,
+    });
+
+    if is_collected {
+        return;
+    }
+
+    let Ok(path) = env::var(RESULTS_PATH_ENV) else {
+        return;
+    };
+
+    if let Err(e) = append_line(&path, &result.to_json_line()) {
+        eprintln!("Failed to write result record to \"{path}\": {e}");
+    }
+}
+
+fn append_line(path: &str, line: &str) -> Result<(), io::Error> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Reads all records from a results file. Missing files yield no records.
+pub fn read_results(path: &Path) -> Vec<PartResult> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return vec![];
+    };
+
+    parse_results(&contents)
+}
+
+fn parse_results(contents: &str) -> Vec<PartResult> {
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| match PartResult::try_from(l) {
+            Ok(result) => Some(result),
+            Err(e) => {
+                eprintln!("Could not parse result record: {e}");
+                None
+            }
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(PROTOCOL_VERSION)),
+        );
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("nanos".into(), JsonValue::Number(value.nanos as f64));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        let (check, expected) = match &value.check {
+            Check::Pass => ("pass", JsonValue::Null),
+            Check::Fail { expected } => ("fail", JsonValue::String(expected.clone())),
+            Check::Unknown => ("unknown", JsonValue::Null),
+            Check::Unreadable => ("unreadable", JsonValue::Null),
+        };
+        map.insert("check".into(), JsonValue::String(check.into()));
+        map.insert("expected".into(), expected);
+        map.insert(
+            "message".into(),
+            match &value.message {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "alloc".into(),
+            match &value.alloc {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for PartResult {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected record to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("expected record.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let version = number("version")? as u32;
+        if version != PROTOCOL_VERSION {
+            return Err(format!(
+                "unsupported record version {version}, expected {PROTOCOL_VERSION}."
+            ));
+        }
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("expected record.status to be a string.")?
+            .parse()?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("expected record.answer to be null or string.")?;
+
+        let stats = match json.get("stats") {
+            Some(v) if !v.is_null() => Some(Stats::try_from(v)?),
+            _ => None,
+        };
+
+        let check = match json.get("check").and_then(|v| v.get::<String>()) {
+            Some(x) if x == "pass" => Check::Pass,
+            Some(x) if x == "fail" => Check::Fail {
+                expected: json
+                    .get("expected")
+                    .and_then(|v| v.get::<String>())
+                    .cloned()
+                    .ok_or("expected record.expected to be a string for failed checks.")?,
+            },
+            Some(x) if x == "unreadable" => Check::Unreadable,
+            _ => Check::Unknown,
+        };
+
+        let alloc = match json.get("alloc") {
+            Some(v) if !v.is_null() => Some(AllocStats::try_from(v)?),
+            _ => None,
+        };
+
+        let message = json.get("message").and_then(|v| v.get::<String>()).cloned();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(PartResult {
+            part: number("part")? as u8,
+            status,
+            answer: answer.cloned(),
+            nanos: number("nanos")? as u64,
+            samples: number("samples")? as u64,
+            stats,
+            check,
+            message,
+            alloc,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartResult, Status, collect, emit, parse_results};
+    use crate::template::{alloc_stats::AllocStats, answers::Check, stats::Stats};
+
+    fn get_mock_result() -> PartResult {
+        PartResult {
+            part: 1,
+            status: Status::Solved,
+            answer: Some("42".into()),
+            nanos: 74_130,
+            samples: 100_000,
+            stats: None,
+            check: Check::Unknown,
+            message: None,
+            alloc: None,
+        }
+    }
+
+    #[test]
+    fn roundtrips_records() {
+        let result = get_mock_result();
+        let line = result.to_json_line();
+        assert_eq!(PartResult::try_from(line.as_str()).unwrap(), result);
+    }
+
+    #[test]
+    fn keeps_multiline_answers_on_one_line() {
+        let result = PartResult {
+            answer: Some("#..#\n#..#\n (1ns @ 5 samples)".into()),
+            ..get_mock_result()
+        };
+        let line = result.to_json_line();
+        assert_eq!(line.lines().count(), 1);
+        assert_eq!(PartResult::try_from(line.as_str()).unwrap(), result);
+    }
+
+    #[test]
+    fn collects_records_in_process() {
+        let results = collect(|| {
+            emit(&get_mock_result());
+            emit(&get_mock_result());
+        });
+        assert_eq!(results, vec![get_mock_result(), get_mock_result()]);
+        assert_eq!(collect(|| {}), vec![]);
+    }
+
+    #[test]
+    fn roundtrips_failures() {
+        for status in [Status::Panic, Status::Timeout] {
+            let result = PartResult {
+                status,
+                answer: None,
+                message: Some("boom at src/bin/2025-01.rs:4:5".into()),
+                ..get_mock_result()
+            };
+            let line = result.to_json_line();
+            assert_eq!(PartResult::try_from(line.as_str()).unwrap(), result);
+        }
+    }
+
+    #[test]
+    fn roundtrips_alloc_stats() {
+        let result = PartResult {
+            alloc: Some(AllocStats {
+                allocations: 3,
+                bytes: 2048,
+                peak_bytes: 1024,
+            }),
+            ..get_mock_result()
+        };
+        let line = result.to_json_line();
+        assert_eq!(PartResult::try_from(line.as_str()).unwrap(), result);
+    }
+
+    #[test]
+    fn roundtrips_stats() {
+        let result = PartResult {
+            stats: Some(Stats {
+                mean: 74_130,
+                min: 70_000,
+                median: 74_000,
+                p95: 80_000,
+                stddev: 1_200,
+                ci_low: 74_000,
+                ci_high: 74_200,
+                samples: 100_000,
+                outliers: 12,
+            }),
+            ..get_mock_result()
+        };
+        let line = result.to_json_line();
+        assert_eq!(PartResult::try_from(line.as_str()).unwrap(), result);
+    }
+
+    #[test]
+    fn roundtrips_checks() {
+        for check in [
+            Check::Pass,
+            Check::Fail {
+                expected: "41".into(),
+            },
+            Check::Unreadable,
+        ] {
+            let result = PartResult {
+                check,
+                ..get_mock_result()
+            };
+            let line = result.to_json_line();
+            assert_eq!(PartResult::try_from(line.as_str()).unwrap(), result);
+        }
+    }
+
+    #[test]
+    fn handles_unsolved_parts() {
+        let line = r#"{ "version": 3, "part": 2, "status": "unsolved", "answer": null, "nanos": 12, "samples": 1 }"#;
+        let result = PartResult::try_from(line).unwrap();
+        assert_eq!(result.status, Status::Unsolved);
+        assert_eq!(result.answer, None);
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        let line = r#"{ "version": 999, "part": 1, "status": "solved", "answer": "1", "nanos": 1, "samples": 1 }"#;
+        assert!(PartResult::try_from(line).is_err());
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let contents = format!(
+            "{}\n\nnot json\n{}\n",
+            get_mock_result().to_json_line(),
+            PartResult {
+                part: 2,
+                ..get_mock_result()
+            }
+            .to_json_line()
+        );
+        let results = parse_results(&contents);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].part, 2);
+    }
+}
@@ -0,0 +1,64 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use crate::template::{Day, Year};
+
+#[cfg(feature = "today")]
+use crate::template::Calendar;
+#[cfg(feature = "today")]
+use chrono::{Datelike, FixedOffset, Utc};
+
+#[cfg(feature = "today")]
+const SERVER_UTC_OFFSET: i32 = -5;
+
+/// Identifies a single puzzle by its year and day.
+///
+/// # Display
+/// This value displays as the name of the puzzle's solution binary.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId, Year};
+/// let puzzle = PuzzleId::new(Year::new(2025).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2025-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Path of a data file of this puzzle, e.g. `data/2025/inputs/08.txt`.
+    pub fn data_path(self, folder: &str, extension: &str) -> PathBuf {
+        PathBuf::from(self.year.data_dir())
+            .join(folder)
+            .join(format!("{}.{extension}", self.day))
+    }
+
+    /// Path of the solution binary of this puzzle, e.g. `src/bin/2025-08.rs`.
+    pub fn bin_path(self) -> String {
+        format!("src/bin/{self}.rs")
+    }
+}
+
+#[cfg(feature = "today")]
+impl PuzzleId {
+    /// Returns the puzzle of the current day if one unlocks on it, `None` otherwise.
+    pub fn today() -> Option<Self> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let today = Utc::now().with_timezone(&offset);
+        let year = Year::new(u16::try_from(today.year()).ok()?)?;
+        let day = Day::new(u8::try_from(today.day()).ok()?)?;
+        (today.month() == 12 && Calendar::new(year).contains(day)).then_some(Self::new(year, day))
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
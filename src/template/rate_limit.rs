@@ -0,0 +1,133 @@
+/// Process-wide rate limiter for requests to the puzzle website.
+///
+/// The time of the last request is kept in memory and persisted to a state file,
+/// so consecutive invocations of the template are throttled as well.
+use std::{
+    fs,
+    path::PathBuf,
+    sync::Mutex,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Minimum time between two requests.
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(3);
+
+static LAST_REQUEST: Mutex<Option<SystemTime>> = Mutex::new(None);
+
+pub struct RateLimiter {
+    interval: Duration,
+    state_path: Option<PathBuf>,
+}
+
+impl RateLimiter {
+    pub fn new(interval: Duration, state_path: Option<PathBuf>) -> Self {
+        Self {
+            interval,
+            state_path,
+        }
+    }
+
+    /// A rate limiter that never waits.
+    pub fn disabled() -> Self {
+        Self::new(Duration::ZERO, None)
+    }
+
+    /// Blocks until `interval` has passed since the last request, then records a new request.
+    pub fn wait(&self) {
+        if self.interval.is_zero() {
+            return;
+        }
+
+        // NOTE: hold the lock while sleeping so concurrent requests queue up.
+        let mut last_request = LAST_REQUEST.lock().unwrap_or_else(|e| e.into_inner());
+
+        let last = [*last_request, self.read_state()]
+            .into_iter()
+            .flatten()
+            .max();
+        let delay = remaining(last, SystemTime::now(), self.interval);
+
+        if !delay.is_zero() {
+            thread::sleep(delay);
+        }
+
+        let now = SystemTime::now();
+        *last_request = Some(now);
+        self.write_state(now);
+    }
+
+    fn read_state(&self) -> Option<SystemTime> {
+        let millis = fs::read_to_string(self.state_path.as_ref()?)
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        Some(UNIX_EPOCH + Duration::from_millis(millis))
+    }
+
+    fn write_state(&self, time: SystemTime) {
+        let Some(path) = &self.state_path else {
+            return;
+        };
+
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+
+        let millis = time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        if let Err(e) = fs::write(path, millis.to_string()) {
+            eprintln!("Failed to persist rate limiter state: {e}");
+        }
+    }
+}
+
+/// Time left to wait before the next request is allowed.
+fn remaining(last: Option<SystemTime>, now: SystemTime, interval: Duration) -> Duration {
+    last.and_then(|last| now.duration_since(last).ok())
+        .map_or(Duration::ZERO, |elapsed| interval.saturating_sub(elapsed))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{RateLimiter, remaining};
+    use std::{
+        env, fs, process,
+        time::{Duration, UNIX_EPOCH},
+    };
+
+    #[test]
+    fn computes_remaining_time() {
+        let interval = Duration::from_secs(3);
+        let last = UNIX_EPOCH + Duration::from_secs(100);
+        assert_eq!(remaining(None, last, interval), Duration::ZERO);
+        assert_eq!(
+            remaining(Some(last), last + Duration::from_secs(1), interval),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            remaining(Some(last), last + Duration::from_secs(5), interval),
+            Duration::ZERO
+        );
+        // clock went backwards, do not wait forever.
+        assert_eq!(
+            remaining(Some(last), last - Duration::from_secs(5), interval),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn persists_last_request() {
+        let path = env::temp_dir().join(format!("aoc-rate-limit-{}", process::id()));
+        let _ = fs::remove_file(&path);
+        let limiter = RateLimiter::new(Duration::from_millis(1), Some(path.clone()));
+        limiter.wait();
+        assert!(limiter.read_state().is_some());
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -1,11 +1,27 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+/// Every table is delimited by a pair of markers, e.g. `<!--- benchmarking table 2025 --->`.
+///
+/// The marker names the section and can configure its layout, e.g.
+/// `<!--- benchmarking table 2025-debug variant=debug heading=3 columns=part_1,part_2,total sort=-total --->`:
+///
+/// - `year`: the year of the timings, defaults to the year the name starts with.
+/// - `variant`: timings stored with `cargo time --store --variant <variant>`, defaults to the default timings.
+/// - `heading`: level of the heading, `0` omits it. Defaults to `2`.
+/// - `columns`: comma-separated list of `parse`, `part_1`, `part_2`, `total`, `alloc` and `stars`.
+///   Defaults to the parts, with parse and heap columns if any day has them.
+/// - `sort`: `day`, `parse`, `part_1`, `part_2` or `total`, prefixed with `-` for descending order.
+/// - `chart`: `false` to not embed the chart, see [`chart`].
+///
+/// The bare `<!--- benchmarking table --->` of older readmes is the default table of the year being timed.
+use std::{cmp::Reverse, collections::HashMap, fs, io, ops::Range, str::FromStr};
 
-use crate::template::Day;
-use crate::template::timings::Timings;
-
-static MARKER: &str = "<!--- benchmarking table --->";
+use crate::template::answers::Answers;
+use crate::template::protocol::Status;
+use crate::template::stats::{Stats, format_nanos};
+use crate::template::timings::{Measurement, Timing, Timings};
+use crate::template::{Day, PuzzleId, Year};
+use crate::template::{chart, history};
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -20,108 +36,550 @@ impl From<std::io::Error> for Error {
     }
 }
 
-pub struct TablePosition {
+static MARKER_START: &str = "<!--- benchmarking table ";
+static MARKER_END: &str = " --->";
+static LEGACY_MARKER: &str = "<!--- benchmarking table --->";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Column {
+    Parse,
+    Part1,
+    Part2,
+    Total,
+    Alloc,
+    Stars,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Column::Parse),
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "total" => Ok(Column::Total),
+            "alloc" => Ok(Column::Alloc),
+            "stars" => Ok(Column::Stars),
+            _ => Err(format!("unknown column `{s}`.")),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SortKey {
+    Day,
+    Parse,
+    Part1,
+    Part2,
+    Total,
+}
+
+impl SortKey {
+    fn nanos(self, timing: &Timing) -> Option<u64> {
+        match self {
+            SortKey::Day => Some(u64::from(timing.day.into_inner())),
+            SortKey::Parse => timing.parse.map(|m| m.nanos),
+            SortKey::Part1 => timing.part_1.map(|m| m.nanos),
+            SortKey::Part2 => timing.part_2.map(|m| m.nanos),
+            SortKey::Total => Some(timing.total_nanos()),
+        }
+    }
+}
+
+impl FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(SortKey::Day),
+            "parse" => Ok(SortKey::Parse),
+            "part_1" => Ok(SortKey::Part1),
+            "part_2" => Ok(SortKey::Part2),
+            "total" => Ok(SortKey::Total),
+            _ => Err(format!("unknown sort order `{s}`.")),
+        }
+    }
+}
+
+/// Layout of a section, read from the options of its marker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Layout {
+    year: Year,
+    variant: Option<String>,
+    heading: usize,
+    /// `None` picks the columns based on the timings.
+    columns: Option<Vec<Column>>,
+    sort: SortKey,
+    descending: bool,
+    chart: bool,
+}
+
+impl Layout {
+    /// Parses the contents of a marker, e.g. `2025-debug variant=debug heading=3`.
+    fn parse(marker: &str) -> Result<Self, String> {
+        let mut words = marker.split_whitespace();
+        let name = words.next().ok_or("benchmark marker without a name.")?;
+
+        let mut year: Option<Year> = name.split('-').next().and_then(|y| y.parse().ok());
+        let mut variant = None;
+        let mut heading = 2;
+        let mut columns = None;
+        let mut sort = SortKey::Day;
+        let mut descending = false;
+        let mut chart = true;
+
+        for option in words {
+            let (key, value) = option.split_once('=').ok_or_else(|| {
+                format!("expected `key=value` in benchmark marker `{name}`, found `{option}`.")
+            })?;
+
+            match key {
+                "year" => {
+                    year = Some(
+                        value
+                            .parse()
+                            .map_err(|_| format!("invalid year `{value}`."))?,
+                    );
+                }
+                "variant" => variant = Some(value.into()),
+                "heading" => {
+                    heading = value
+                        .parse()
+                        .ok()
+                        .filter(|level| *level <= 6)
+                        .ok_or_else(|| format!("invalid heading level `{value}`."))?;
+                }
+                "columns" => {
+                    columns = Some(
+                        value
+                            .split(',')
+                            .map(Column::from_str)
+                            .collect::<Result<_, _>>()?,
+                    );
+                }
+                "sort" => {
+                    descending = value.starts_with('-');
+                    sort = value.trim_start_matches('-').parse()?;
+                }
+                "chart" => chart = value != "false",
+                _ => {
+                    return Err(format!(
+                        "unknown option `{key}` in benchmark marker `{name}`."
+                    ));
+                }
+            }
+        }
+
+        let year = year.ok_or_else(|| {
+            format!("benchmark marker `{name}` does not start with a year, add `year=<year>`.")
+        })?;
+
+        Ok(Layout {
+            year,
+            variant,
+            heading,
+            columns,
+            sort,
+            descending,
+            chart,
+        })
+    }
+
+    fn matches(&self, year: Year, variant: Option<&str>) -> bool {
+        self.year == year && self.variant.as_deref() == variant
+    }
+}
+
+/// A table in the readme, spanning from its first marker to its last.
+struct Section {
+    marker: String,
+    layout: Layout,
     pos_start: usize,
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+fn get_marker(year: Year) -> String {
+    format!("{MARKER_START}{year}{MARKER_END}")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Byte ranges of the fenced code blocks and inline code of the readme.
+/// Markers in them are documentation, not sections.
+pub fn get_code_ranges(readme: &str) -> Vec<Range<usize>> {
+    let mut ranges = vec![];
+    let mut fence_start: Option<usize> = None;
+    let mut offset = 0;
 
-    if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+    for line in readme.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            match fence_start.take() {
+                Some(start) => ranges.push(start..offset + line.len()),
+                None => fence_start = Some(offset),
+            }
+        } else if fence_start.is_none() {
+            let ticks: Vec<usize> = line.match_indices('`').map(|(i, _)| offset + i).collect();
+            ranges.extend(ticks.chunks_exact(2).map(|pair| pair[0]..pair[1]));
+        }
+        offset += line.len();
     }
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+    // an unclosed fence runs to the end of the document.
+    if let Some(start) = fence_start {
+        ranges.push(start..readme.len());
+    }
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+    ranges
+}
 
-    Ok(TablePosition { pos_start, pos_end })
+/// The name and options of a marker, empty for the legacy marker.
+fn marker_contents(marker: &str) -> &str {
+    if marker == LEGACY_MARKER {
+        ""
+    } else {
+        &marker[MARKER_START.len()..marker.len() - MARKER_END.len()]
+    }
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn locate_sections(readme: &str, year: Year) -> Result<Vec<Section>, Error> {
+    // markers grouped by the name of their section, in order of appearance.
+    let mut groups: Vec<(String, Vec<(usize, usize)>)> = vec![];
+    let mut offset = 0;
+    let code = get_code_ranges(readme);
+
+    while let Some(start) = readme[offset..].find(MARKER_START).map(|i| i + offset) {
+        let end = if readme[start..].starts_with(LEGACY_MARKER) {
+            start + LEGACY_MARKER.len()
+        } else {
+            let content_start = start + MARKER_START.len();
+            readme[content_start..]
+                .find(MARKER_END)
+                .map(|i| i + content_start + MARKER_END.len())
+                .ok_or_else(|| Error::Parser("unterminated benchmark marker in README.".into()))?
+        };
+        offset = end;
+
+        if code.iter().any(|range| range.contains(&start)) {
+            continue;
+        }
+
+        let name = marker_contents(&readme[start..end])
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        match groups.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, positions)) => positions.push((start, end)),
+            None => groups.push((name, vec![(start, end)])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(name, positions)| {
+            if positions.len() > 2 {
+                return Err(Error::Parser(format!(
+                    "{name}: too many occurences of marker in README."
+                )));
+            }
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+            let (pos_start, first_end) = positions[0];
+            let pos_end = positions[positions.len() - 1].1;
+            let marker = readme[pos_start..first_end].to_string();
+            let layout = match marker_contents(&marker) {
+                "" => Layout::parse(&year.to_string()),
+                contents => Layout::parse(contents),
+            }
+            .map_err(Error::Parser)?;
+
+            Ok(Section {
+                marker,
+                layout,
+                pos_start,
+                pos_end,
+            })
+        })
+        .collect()
+}
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+fn construct_table(
+    section: &Section,
+    timings: &Timings,
+    total_millis: f64,
+    chart_path: Option<&str>,
+    stars: &HashMap<Day, usize>,
+) -> String {
+    let layout = &section.layout;
+    let year = layout.year;
+
+    let columns = layout.columns.clone().unwrap_or_else(|| {
+        let mut columns = vec![];
+        // only render a parse column when at least one solution uses a parse hook.
+        if timings.data.iter().any(|t| t.parse.is_some()) {
+            columns.push(Column::Parse);
+        }
+        columns.extend([Column::Part1, Column::Part2]);
+        // heap columns are only rendered for timings recorded with the `alloc-stats` feature.
+        if timings
+            .data
+            .iter()
+            .any(|t| t.part_1_alloc.is_some() || t.part_2_alloc.is_some())
+        {
+            columns.push(Column::Alloc);
+        }
+        columns
+    });
+
+    let mut headers = vec!["Day"];
+    for column in &columns {
+        headers.extend(match column {
+            Column::Parse => &["Parse"][..],
+            Column::Part1 => &["Part 1"],
+            Column::Part2 => &["Part 2"],
+            Column::Total => &["Total"],
+            Column::Alloc => &["Part 1 Heap", "Part 2 Heap"],
+            Column::Stars => &["Stars"],
+        });
+    }
+
+    let mut lines: Vec<String> = vec![section.marker.clone()];
+    if layout.heading > 0 {
+        let variant = layout
+            .variant
+            .as_ref()
+            .map(|v| format!(" ({v})"))
+            .unwrap_or_default();
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            "{} {year} Benchmarks{variant}",
+            "#".repeat(layout.heading)
         ));
+        lines.push(String::new());
+    }
+    if let Some(path) = chart_path.filter(|_| layout.chart) {
+        lines.push(format!("![{year} benchmarks](./{path})"));
+        lines.push(String::new());
+    }
+    lines.push(format!("| {} |", headers.join(" | ")));
+    lines.push(format!("| {}  |", vec![":---:"; headers.len()].join(" | ")));
+
+    let mut rows: Vec<&Timing> = timings.data.iter().collect();
+    // days without a value for the sort key go last in either order.
+    if layout.descending {
+        rows.sort_by_key(|t| {
+            (
+                layout.sort.nanos(t).is_none(),
+                Reverse(layout.sort.nanos(t)),
+            )
+        });
+    } else {
+        rows.sort_by_key(|t| (layout.sort.nanos(t).is_none(), layout.sort.nanos(t)));
+    }
+
+    for timing in rows {
+        let path = format!("./{}", PuzzleId::new(year, timing.day).bin_path());
+
+        let mut cells = vec![format!("[Day {}]({})", timing.day.into_inner(), path)];
+        for column in &columns {
+            match column {
+                Column::Parse => cells.push(format!(
+                    "`{}`",
+                    format_cell(timing.parse, timing.parse_stats, timing.parse_failure)
+                )),
+                Column::Part1 => cells.push(format!(
+                    "`{}`",
+                    format_cell(timing.part_1, timing.part_1_stats, timing.part_1_failure)
+                )),
+                Column::Part2 => cells.push(format!(
+                    "`{}`",
+                    format_cell(timing.part_2, timing.part_2_stats, timing.part_2_failure)
+                )),
+                Column::Total => cells.push(match timing.total_nanos() {
+                    0 => "`-`".into(),
+                    nanos => format!("`{}`", format_nanos(nanos)),
+                }),
+                Column::Alloc => {
+                    for alloc in [timing.part_1_alloc, timing.part_2_alloc] {
+                        cells.push(format!(
+                            "`{}`",
+                            alloc.map_or_else(|| "-".into(), |alloc| alloc.summary())
+                        ));
+                    }
+                }
+                Column::Stars => cells.push(match stars.get(&timing.day) {
+                    Some(&count) if count > 0 => "⭐".repeat(count),
+                    _ => "-".into(),
+                }),
+            }
+        }
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(section.marker.clone());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+fn format_cell(part: Option<Measurement>, stats: Option<Stats>, failure: Option<Status>) -> String {
+    if let Some(failure) = failure {
+        return failure.as_str().into();
+    }
+
+    match (part, stats) {
+        (Some(part), Some(stats)) => format!("{part} ± {}", format_nanos(stats.stddev)),
+        (Some(part), None) => part.to_string(),
+        (None, _) => "-".into(),
+    }
+}
+
+/// Rewrites every section of `year` and `variant`, fails if there is none.
+fn update_content(
+    s: &mut String,
+    year: Year,
+    variant: Option<&str>,
+    timings: &Timings,
+    chart_path: Option<&str>,
+    stars: &HashMap<Day, usize>,
+) -> Result<(), Error> {
+    let sections: Vec<Section> = locate_sections(s, year)?
+        .into_iter()
+        .filter(|section| section.layout.matches(year, variant))
+        .collect();
+
+    if sections.is_empty() {
+        let marker = match variant {
+            Some(variant) => {
+                format!("{MARKER_START}{year}-{variant} variant={variant}{MARKER_END}")
+            }
+            None => get_marker(year),
+        };
+        return Err(Error::Parser(format!(
+            "Could not find table start position, add \"{marker}\" to the README."
+        )));
+    }
+
+    let total_millis = timings.total_millis();
+
+    // replace back to front, so the positions of earlier sections stay valid.
+    for section in sections.iter().rev() {
+        let table = construct_table(section, timings, total_millis, chart_path, stars);
+        s.replace_range(section.pos_start..section.pos_end, &table);
+    }
+
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Rewrites the tables of `year` in the readme, along with the chart they embed, see [`chart`].
+pub fn update(year: Year, variant: Option<&str>, timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let chart_path = chart::store_file(year, variant, &timings, &history::read(year, variant))?;
+
+    // a star is earned for every part with an accepted answer.
+    let stars = timings
+        .data
+        .iter()
+        .map(|t| {
+            let answers = Answers::read(PuzzleId::new(year, t.day)).unwrap_or_default();
+            (
+                t.day,
+                [1, 2]
+                    .iter()
+                    .filter(|&&part| answers.get(part).is_some())
+                    .count(),
+            )
+        })
+        .collect();
+
+    update_content(
+        &mut readme,
+        year,
+        variant,
+        &timings,
+        Some(&chart_path),
+        &stars,
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{Error, update_content};
+    use crate::{
+        day, template::Year, template::alloc_stats::AllocStats, template::protocol::Status,
+        template::stats::Stats, template::timings::Measurement, template::timings::Timing,
+        template::timings::Timings, year,
+    };
+    use std::collections::HashMap;
+
+    const MARKER: &str = "<!--- benchmarking table 2025 --->";
+
+    fn update(s: &mut String, year: Year, timings: &Timings) -> Result<(), Error> {
+        update_content(s, year, None, timings, None, &HashMap::new())
+    }
+
+    fn millis(millis: u64) -> Measurement {
+        Measurement {
+            nanos: millis * 1_000_000,
+            samples: 100,
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    parse: None,
+                    parse_stats: None,
+                    part_1: Some(millis(10)),
+                    part_2: Some(millis(20)),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_failure: None,
+                    part_1_failure: None,
+                    part_2_failure: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    parse: None,
+                    parse_stats: None,
+                    part_1: Some(millis(30)),
+                    part_2: Some(millis(40)),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_failure: None,
+                    part_1_failure: None,
+                    part_2_failure: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    parse: None,
+                    parse_stats: None,
+                    part_1: Some(millis(40)),
+                    part_2: Some(millis(50)),
+                    part_1_stats: Some(Stats {
+                        mean: 40_000_000,
+                        stddev: 1_500_000,
+                        ..Stats::default()
+                    }),
+                    part_2_stats: None,
+                    parse_failure: None,
+                    part_1_failure: None,
+                    part_2_failure: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 },
             ],
         }
@@ -131,53 +589,228 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, year!(2025), &get_mock_timings()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, year!(2025), &get_mock_timings()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update(&mut s, year!(2025), &get_mock_timings()).unwrap();
+        assert_eq!(s.contains("## 2025 Benchmarks"), true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_of_year_not_present() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update(&mut s, year!(2024), &get_mock_timings()).unwrap();
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, year!(2025), &get_mock_timings()).unwrap();
+        update(&mut s, year!(2025), &get_mock_timings()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        assert_eq!(
+            s.matches("## 2025 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
+    }
+
+    #[test]
+    fn updates_legacy_marker() {
+        let legacy = "<!--- benchmarking table --->";
+        let debug = "<!--- benchmarking table 2025-debug variant=debug --->";
+        let mut s = format!("foo\n{legacy}\n{legacy}\n{debug}\n{debug}\nbaz");
+        update(&mut s, year!(2025), &get_mock_timings()).unwrap();
+        update(&mut s, year!(2025), &get_mock_timings()).unwrap();
+        assert_eq!(s.matches(legacy).count(), 2);
+        assert_eq!(s.matches(debug).count(), 2);
+        assert_eq!(s.matches("## 2025 Benchmarks").count(), 1);
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse_column() {
+        let mut timings = get_mock_timings();
+        timings.data[0].parse = Some(millis(5));
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update(&mut s, year!(2025), &timings).unwrap();
+        assert_eq!(s.contains("| Day | Parse | Part 1 | Part 2 |"), true);
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/2025-01.rs) | `5.0ms` | `10.0ms` | `20.0ms` |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/2025-02.rs) | `-` | `30.0ms` | `40.0ms` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_benchmarks_with_chart() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            year!(2025),
+            None,
+            &get_mock_timings(),
+            Some("data/2025/benchmarks.svg"),
+            &HashMap::new(),
+        )
+        .unwrap();
+        assert_eq!(
+            s.contains(
+                "## 2025 Benchmarks\n\n![2025 benchmarks](./data/2025/benchmarks.svg)\n\n| Day |"
+            ),
+            true
+        );
+    }
+
+    #[test]
+    fn format_failed_parts() {
+        let mut timings = get_mock_timings();
+        timings.data[1].part_2 = None;
+        timings.data[1].part_2_failure = Some(Status::Timeout);
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update(&mut s, year!(2025), &timings).unwrap();
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/2025-02.rs) | `30.0ms` | `timeout` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_benchmarks_with_heap_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_alloc = Some(AllocStats {
+            allocations: 12,
+            bytes: 4096,
+            peak_bytes: 1024,
+        });
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update(&mut s, year!(2025), &timings).unwrap();
+        assert_eq!(
+            s.contains("| Day | Part 1 | Part 2 | Part 1 Heap | Part 2 Heap |"),
+            true
+        );
+        assert_eq!(
+            s.contains(
+                "| [Day 1](./src/bin/2025-01.rs) | `10.0ms` | `20.0ms` | `12 allocs · 4.0 KiB · peak 1.0 KiB` | `-` |"
+            ),
+            true
+        );
     }
 
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, year!(2025), &get_mock_timings()).unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "<!--- benchmarking table 2025 --->",
+            "## 2025 Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2025-01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2025-02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2025-04.rs) | `40.0ms ± 1.5ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
-            "<!--- benchmarking table --->",
+            "<!--- benchmarking table 2025 --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_layout() {
+        let marker =
+            "<!--- benchmarking table 2025 heading=3 columns=part_1,total,stars sort=-total --->";
+        let mut s = format!("{marker}\n{marker}");
+        let stars = HashMap::from([(day!(1), 2), (day!(4), 1)]);
+        update_content(&mut s, year!(2025), None, &get_mock_timings(), None, &stars).unwrap();
+        let expected = [
+            marker,
+            "### 2025 Benchmarks",
+            "",
+            "| Day | Part 1 | Total | Stars |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 4](./src/bin/2025-04.rs) | `40.0ms ± 1.5ms` | `90.0ms` | ⭐ |",
+            "| [Day 2](./src/bin/2025-02.rs) | `30.0ms` | `70.0ms` | - |",
+            "| [Day 1](./src/bin/2025-01.rs) | `10.0ms` | `30.0ms` | ⭐⭐ |",
+            "",
+            "**Total: 190.00ms**",
+            marker,
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn updates_named_sections_of_variant() {
+        let release = "<!--- benchmarking table 2025 --->";
+        let debug = "<!--- benchmarking table 2025-debug variant=debug heading=0 --->";
+        let mut s = format!("{release}{release}\n{debug}\nold table\n{debug}\n");
+
+        update_content(
+            &mut s,
+            year!(2025),
+            Some("debug"),
+            &get_mock_timings(),
+            None,
+            &HashMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            s.starts_with(&format!("{release}{release}\n{debug}\n| Day |")),
+            true
+        );
+        assert_eq!(s.contains("old table"), false);
+        assert_eq!(s.contains("Benchmarks"), false);
+
+        update(&mut s, year!(2025), &get_mock_timings()).unwrap();
+        assert_eq!(s.matches("## 2025 Benchmarks").count(), 1);
+        assert_eq!(s.matches(debug).count(), 2);
+    }
+
+    #[test]
+    fn ignores_markers_in_code() {
+        let docs = format!("Add `{MARKER}` markers:\n\n```md\n{MARKER}\n{MARKER}\n```\n");
+        let mut s = format!("{MARKER}{MARKER}\n{docs}");
+        update(&mut s, year!(2025), &get_mock_timings()).unwrap();
+        assert_eq!(s.matches("## 2025 Benchmarks").count(), 1);
+        assert_eq!(s.ends_with(&docs), true);
+    }
+
+    #[test]
+    fn errors_for_invalid_layouts() {
+        for marker in [
+            "<!--- benchmarking table 2025 columns=part_3 --->",
+            "<!--- benchmarking table 2025 heading=7 --->",
+            "<!--- benchmarking table 2025 sort=fastest --->",
+            "<!--- benchmarking table 2025 colour=red --->",
+            "<!--- benchmarking table debug --->",
+        ] {
+            let mut s = marker.to_string();
+            assert!(
+                update(&mut s, year!(2025), &get_mock_timings()).is_err(),
+                "{marker}"
+            );
+        }
+
+        let mut s = "<!--- benchmarking table debug year=2025 --->".to_string();
+        assert!(update(&mut s, year!(2025), &get_mock_timings()).is_ok());
+    }
 }
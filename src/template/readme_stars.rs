@@ -0,0 +1,138 @@
+/// Module that updates a badge with the collected stars of a year in the readme.
+/// Every year has its own badge, delimited by e.g. `<!--- stars badge 2025 --->`.
+use std::fs;
+
+use crate::template::{
+    Year,
+    progress::Progress,
+    readme_benchmarks::{Error, get_code_ranges},
+};
+
+fn get_marker(year: Year) -> String {
+    format!("<!--- stars badge {year} --->")
+}
+
+/// Link to a [shields.io](https://shields.io) badge, e.g. `2025 | 14/24 ⭐`.
+fn badge(progress: &Progress) -> String {
+    let (stars, max_stars) = (progress.stars(), progress.max_stars());
+    let color = if stars == max_stars {
+        "brightgreen"
+    } else {
+        "yellow"
+    };
+    format!(
+        "![{} stars](https://img.shields.io/badge/{}-{stars}%2F{max_stars}%20%E2%AD%90-{color})",
+        progress.year, progress.year
+    )
+}
+
+fn update_content(s: &mut String, progress: &Progress) -> Result<(), Error> {
+    let marker = get_marker(progress.year);
+    let code = get_code_ranges(s);
+    let matches: Vec<_> = s
+        .match_indices(&marker)
+        .map(|m| m.0)
+        .filter(|pos| !code.iter().any(|range| range.contains(pos)))
+        .collect();
+
+    if matches.len() > 2 {
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
+    }
+
+    let (Some(&pos_start), Some(&last)) = (matches.first(), matches.last()) else {
+        return Err(Error::Parser(format!(
+            "Could not find badge position, add \"{marker}\" to the README."
+        )));
+    };
+
+    let section = [marker.as_str(), &badge(progress), marker.as_str()].join("\n");
+    s.replace_range(pos_start..last + marker.len(), &section);
+    Ok(())
+}
+
+pub fn update(progress: &Progress) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, progress)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::update_content;
+    use crate::{
+        template::{
+            Day,
+            progress::{DayProgress, PartState, Progress},
+        },
+        year,
+    };
+
+    const MARKER: &str = "<!--- stars badge 2025 --->";
+
+    fn get_mock_progress(stars: u8) -> Progress {
+        Progress {
+            year: year!(2025),
+            days: (1..=12)
+                .map(|day| {
+                    let state = |part: u8| {
+                        if (day - 1) * 2 + part <= stars {
+                            PartState::Solved
+                        } else {
+                            PartState::Open
+                        }
+                    };
+                    DayProgress {
+                        day: Day::new(day).unwrap(),
+                        part_1: state(1),
+                        part_2: state(2),
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn updates_badge() {
+        let mut s = format!("foo\n{MARKER}\nbar");
+        update_content(&mut s, &get_mock_progress(14)).unwrap();
+        update_content(&mut s, &get_mock_progress(15)).unwrap();
+        assert_eq!(
+            s,
+            format!(
+                "foo\n{MARKER}\n![2025 stars](https://img.shields.io/badge/2025-15%2F24%20%E2%AD%90-yellow)\n{MARKER}\nbar"
+            )
+        );
+    }
+
+    #[test]
+    fn colors_complete_years() {
+        let mut s = MARKER.to_string();
+        update_content(&mut s, &get_mock_progress(24)).unwrap();
+        assert!(s.contains("24%2F24%20%E2%AD%90-brightgreen"));
+    }
+
+    #[test]
+    fn ignores_markers_in_code() {
+        let docs = format!("Add `{MARKER}` markers.");
+        let mut s = docs.clone();
+        assert!(update_content(&mut s, &get_mock_progress(1)).is_err());
+
+        let mut s = format!("{MARKER}\n{MARKER}\n{docs}");
+        update_content(&mut s, &get_mock_progress(1)).unwrap();
+        assert!(s.ends_with(&docs));
+        assert_eq!(s.matches("img.shields.io").count(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_progress(1)).unwrap();
+    }
+}
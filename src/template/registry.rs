@@ -0,0 +1,78 @@
+/// Registry of the solutions compiled into the `all-days` binary.
+///
+/// `build.rs` includes every `src/bin/{year}-{day}.rs` as a module of that binary, which installs them here.
+/// `cargo all` and `cargo time` then run all days from this single build instead of invoking cargo once per day.
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::OnceLock,
+};
+
+use crate::template::{
+    PuzzleId,
+    protocol::{self, PartResult},
+    read_file, runner,
+};
+
+/// A solution that can be run in-process.
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    /// Runs every part of the solution on an input, see [`crate::solution`].
+    pub run: fn(&str),
+}
+
+static SOLUTIONS: OnceLock<&'static [Solution]> = OnceLock::new();
+
+/// Makes `solutions` available to [`crate::template::commands::all`] and [`crate::template::commands::time`].
+/// Without a registry, both commands fall back to running every day as its own binary.
+/// An empty registry is not installed, e.g. with `dhat-heap`, where `build.rs` leaves out every solution.
+pub fn install(solutions: &'static [Solution]) {
+    if !solutions.is_empty() {
+        let _ = SOLUTIONS.set(solutions);
+    }
+}
+
+pub(crate) fn is_installed() -> bool {
+    SOLUTIONS.get().is_some()
+}
+
+/// Whether the installed solutions were built with the profile `is_release` asks for, so they can run in-process.
+pub(crate) fn is_installed_for(is_release: bool) -> bool {
+    is_installed() && is_release != cfg!(debug_assertions)
+}
+
+pub(crate) fn contains(puzzle: PuzzleId) -> bool {
+    get(puzzle).is_some()
+}
+
+fn get(puzzle: PuzzleId) -> Option<&'static Solution> {
+    SOLUTIONS.get()?.iter().find(|s| s.puzzle == puzzle)
+}
+
+/// Runs the solution of `puzzle` like its own binary would, used for days that run in a separate process.
+/// Returns `false` if there is no solution for `puzzle`.
+pub fn run_day(puzzle: PuzzleId) -> bool {
+    let Some(solution) = get(puzzle) else {
+        return false;
+    };
+
+    (solution.run)(&read_file("inputs", puzzle));
+    true
+}
+
+/// Runs the solution of `puzzle` and returns the records of its parts.
+/// A panic only aborts the current day, records of parts that ran before it are kept.
+pub(crate) fn run_solution(puzzle: PuzzleId, is_timed: bool) -> Vec<PartResult> {
+    let Some(solution) = get(puzzle) else {
+        return vec![];
+    };
+
+    runner::set_timed(is_timed);
+
+    protocol::collect(|| {
+        // NOTE: the panic hook already printed the message and location.
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| {
+            let input = read_file("inputs", puzzle);
+            (solution.run)(&input);
+        }));
+    })
+}
@@ -1,40 +1,144 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+use crate::template::{
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, PuzzleId, Year,
+    protocol::{self, PartResult, Status},
+};
 
 use super::{
-    all_days,
+    all_days, guard, registry,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Aggregated outcome of running multiple days.
+pub struct Summary {
+    /// Benchmark timings, only present for timed runs.
+    pub timings: Option<Timings>,
+    /// Parts whose answer did not match the answer registry.
+    pub failed_checks: Vec<(Day, u8)>,
+    /// Parts that panicked or timed out.
+    pub failures: Vec<(Day, u8, Status)>,
+}
+
+/// Output of a day that ran in the background, printed once all previous days are done.
+struct DayRun {
+    day: Day,
+    stdout: String,
+    stderr: String,
+    results: Vec<PartResult>,
+}
+
+/// Runs the selected days of `year`. With more than one job, days run concurrently and
+/// their output is buffered, then printed in order of days.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+) -> Summary {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut failed_checks: Vec<(Day, u8)> = vec![];
+    let mut failures: Vec<(Day, u8, Status)> = vec![];
 
     let mut need_space = false;
+    let mut print_header = |day: Day| {
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+    };
+
+    let mut add_results = |day: Day, results: &[PartResult]| {
+        if results.is_empty() {
+            println!("Not solved.");
+        } else {
+            failed_checks.extend(
+                results
+                    .iter()
+                    .filter(|r| r.check.is_failure())
+                    .map(|r| (day, r.part)),
+            );
+            failures.extend(
+                results
+                    .iter()
+                    .filter(|r| matches!(r.status, Status::Panic | Status::Timeout))
+                    .map(|r| (day, r.part, r.status)),
+            );
+            let val = child_commands::parse_exec_time(results, day);
+            timings.push(val);
+        }
+    };
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let puzzles: Vec<PuzzleId> = all_days(year)
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+        .map(|day| PuzzleId::new(year, day))
+        .collect();
+
+    if jobs > 1 {
+        run_parallel(&puzzles, is_release, is_timed, jobs, |run| {
+            print_header(run.day);
+            print!("{}", run.stdout);
+            eprint!("{}", run.stderr);
+            add_results(run.day, &run.results);
+        });
+    } else {
+        for puzzle in puzzles {
+            print_header(puzzle.day);
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            // NOTE: a timed out part ends its process, so days with a timeout can not run in-process.
+            let results = if registry::is_installed_for(is_release) && guard::timeout().is_none() {
+                registry::run_solution(puzzle, is_timed)
+            } else {
+                child_commands::run_solution(puzzle, is_timed, is_release).unwrap()
+            };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            add_results(puzzle.day, &results);
+        }
+    }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+    if !failed_checks.is_empty() {
+        let parts: Vec<String> = failed_checks
+            .iter()
+            .map(|(day, part)| format!("Day {day} part {part}"))
+            .collect();
+        println!(
+            "\n{ANSI_BOLD}✘ Answers do not match the answer registry:{ANSI_RESET} {}",
+            parts.join(", ")
+        );
+    }
 
-    if is_timed {
+    if !failures.is_empty() {
+        let parts: Vec<String> = failures
+            .iter()
+            .map(|(day, part, status)| {
+                let part = if *part == protocol::PARSE_PART {
+                    "parse".into()
+                } else {
+                    format!("part {part}")
+                };
+                format!("Day {day} {part} ({})", status.as_str())
+            })
+            .collect();
+        println!(
+            "\n{ANSI_BOLD}✘ Solutions failed:{ANSI_RESET} {}",
+            parts.join(", ")
+        );
+    }
+
+    let timings = if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -43,9 +147,66 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    Summary {
+        timings,
+        failed_checks,
+        failures,
     }
 }
 
+/// Runs `puzzles` on `jobs` threads, each driving one solution process at a time.
+/// `on_done` is called in the order of `puzzles`, as soon as a day and all days before it finished.
+fn run_parallel(
+    puzzles: &[PuzzleId],
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+    mut on_done: impl FnMut(DayRun),
+) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(puzzles.len()) {
+            let sender = sender.clone();
+            let next = &next;
+
+            scope.spawn(move || {
+                while let Some(&puzzle) = puzzles.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let run = child_commands::run_solution_buffered(puzzle, is_timed, is_release)
+                        .unwrap_or_else(|e| DayRun {
+                            day: puzzle.day,
+                            stdout: String::new(),
+                            stderr: format!("Failed to run {puzzle}: {e:?}\n"),
+                            results: vec![],
+                        });
+
+                    if sender.send(run).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        drop(sender);
+
+        let mut pending: BTreeMap<Day, DayRun> = BTreeMap::new();
+        let mut remaining = puzzles.iter().map(|puzzle| puzzle.day);
+        let mut next_day = remaining.next();
+
+        for run in receiver {
+            pending.insert(run.day, run);
+
+            while let Some(run) = next_day.and_then(|day| pending.remove(&day)) {
+                on_done(run);
+                next_day = remaining.next();
+            }
+        }
+    });
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -59,48 +220,125 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// Without a [`registry`], every solution runs as an isolated binary. Parallel runs start one process per day
+/// even with a registry, so that each day's output can be captured.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their result records.
 pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use super::{DayRun, Error};
+    use crate::template::{
+        Day, PuzzleId,
+        protocol::{self, PartResult, Status},
+        registry,
+        timings::Measurement,
+    };
     use std::{
+        env, fs,
         io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        path::{Path, PathBuf},
+        process::{self, Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    /// Where a child process finds the solution of a day.
+    #[derive(Debug)]
+    enum Launcher {
+        /// The current binary, which contains the solution and was built with the requested profile.
+        CurrentExe(PathBuf),
+        /// A build of `all-days` with the requested profile, for runs with another profile than the current binary.
+        AllDays,
+        /// The binary of the solution.
+        Bin,
+    }
+
+    /// Builds the command that runs the solution of `puzzle`, `None` if it does not exist yet.
+    fn solution_command(
+        puzzle: PuzzleId,
+        is_timed: bool,
+        is_release: bool,
+    ) -> Result<Option<Command>, Error> {
+        let launcher = if registry::is_installed() {
+            if !registry::contains(puzzle) {
+                return Ok(None);
+            }
+
+            // the current binary already contains the solution, see `registry::run_day`.
+            if registry::is_installed_for(is_release) {
+                Launcher::CurrentExe(env::current_exe()?)
+            } else {
+                Launcher::AllDays
+            }
+        } else {
+            // skip command invocation for days that have not been scaffolded yet.
+            if !Path::new(&puzzle.bin_path()).exists() {
+                return Ok(None);
+            }
+            Launcher::Bin
+        };
+
+        Ok(Some(build_command(puzzle, &launcher, is_timed, is_release)))
+    }
+
+    fn build_command(
+        puzzle: PuzzleId,
+        launcher: &Launcher,
+        is_timed: bool,
+        is_release: bool,
+    ) -> Command {
+        let mut cmd = match launcher {
+            Launcher::CurrentExe(exe) => Command::new(exe),
+            Launcher::AllDays | Launcher::Bin => Command::new("cargo"),
+        };
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin = match launcher {
+            Launcher::CurrentExe(_) => None,
+            Launcher::AllDays => Some("all-days".to_string()),
+            Launcher::Bin => Some(puzzle.to_string()),
+        };
 
-        if is_release {
-            args.push("--release");
+        if let Some(bin) = bin {
+            cmd.args(["run", "--quiet", "--bin", &bin]);
+            if is_release {
+                cmd.arg("--release");
+            }
+            cmd.arg("--");
+        }
+
+        if !matches!(launcher, Launcher::Bin) {
+            cmd.args([
+                "day",
+                &puzzle.day.to_string(),
+                "--year",
+                &puzzle.year.to_string(),
+            ]);
         }
 
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            cmd.arg("--time");
         }
 
+        cmd
+    }
+
+    /// Run the solution bin for a given puzzle and return the result records it emitted.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        is_timed: bool,
+        is_release: bool,
+    ) -> Result<Vec<PartResult>, Error> {
+        let Some(mut cmd) = solution_command(puzzle, is_timed, is_release)? else {
+            return Ok(vec![]);
+        };
+
+        // the child appends one record per part to this file.
+        let results_path = get_results_path(puzzle);
+        let _ = fs::remove_file(&results_path);
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
+        let mut cmd = cmd
+            .env(protocol::RESULTS_PATH_ENV, &results_path)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -108,8 +346,6 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
         let thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
                 eprintln!("{}", line.unwrap());
@@ -117,139 +353,259 @@ pub mod child_commands {
         });
 
         for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            println!("{}", line.unwrap());
         }
 
         thread.join().unwrap();
         cmd.wait()?;
 
-        Ok(output)
+        let results = protocol::read_results(&results_path);
+        let _ = fs::remove_file(&results_path);
+
+        Ok(results)
+    }
+
+    /// Like [`run_solution`], but collects the output of the solution instead of forwarding it.
+    pub(super) fn run_solution_buffered(
+        puzzle: PuzzleId,
+        is_timed: bool,
+        is_release: bool,
+    ) -> Result<DayRun, Error> {
+        let mut run = DayRun {
+            day: puzzle.day,
+            stdout: String::new(),
+            stderr: String::new(),
+            results: vec![],
+        };
+
+        let Some(mut cmd) = solution_command(puzzle, is_timed, is_release)? else {
+            return Ok(run);
+        };
+
+        let results_path = get_results_path(puzzle);
+        let _ = fs::remove_file(&results_path);
+
+        let output = cmd
+            .env(protocol::RESULTS_PATH_ENV, &results_path)
+            .stdin(Stdio::null())
+            .output()?;
+
+        run.stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+        run.stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+        run.results = protocol::read_results(&results_path);
+        let _ = fs::remove_file(&results_path);
+
+        Ok(run)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    fn get_results_path(puzzle: PuzzleId) -> PathBuf {
+        env::temp_dir().join(format!("aoc-results-{}-{puzzle}.jsonl", process::id()))
+    }
+
+    pub fn parse_exec_time(results: &[PartResult], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            parse: None,
+            parse_stats: None,
             part_1: None,
             part_2: None,
-            total_nanos: 0_f64,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse_failure: None,
+            part_1_failure: None,
+            part_2_failure: None,
+            parse_alloc: None,
+            part_1_alloc: None,
+            part_2_alloc: None,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        // failed steps have no timing, but are recorded so the table can show them.
+        for r in results {
+            let failure = matches!(r.status, Status::Panic | Status::Timeout).then_some(r.status);
+            match r.part {
+                protocol::PARSE_PART => timings.parse_failure = failure,
+                1 => timings.part_1_failure = failure,
+                2 => timings.part_2_failure = failure,
+                _ => {}
+            }
+        }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+        results
+            .iter()
+            .filter(|r| r.status == Status::Solved)
+            .for_each(|r| {
+                let measurement = Some(Measurement {
+                    nanos: r.nanos,
+                    samples: r.samples,
+                });
+
+                match r.part {
+                    protocol::PARSE_PART => {
+                        timings.parse = measurement;
+                        timings.parse_stats = r.stats;
+                        timings.parse_alloc = r.alloc;
+                    }
+                    1 => {
+                        timings.part_1 = measurement;
+                        timings.part_1_stats = r.stats;
+                        timings.part_1_alloc = r.alloc;
+                    }
+                    2 => {
+                        timings.part_2 = measurement;
+                        timings.part_2_stats = r.stats;
+                        timings.part_2_alloc = r.alloc;
+                    }
+                    _ => {}
                 }
-
-                timings.total_nanos += nanos;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+    #[cfg(feature = "test_lib")]
+    mod tests {
+        use super::{Launcher, build_command, parse_exec_time};
+
+        use crate::template::PuzzleId;
+        use crate::template::answers::Check;
+        use crate::template::protocol::{PartResult, Status};
+        use crate::{day, year};
+        use std::path::PathBuf;
+        use std::process::Command;
+
+        fn get_args(cmd: &Command) -> Vec<String> {
+            cmd.get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect()
+        }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .next_back()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
+        #[test]
+        fn builds_commands_per_profile() {
+            let puzzle = PuzzleId::new(year!(2025), day!(8));
+
+            for is_release in [false, true] {
+                let release: &[&str] = if is_release { &["--release"] } else { &[] };
+
+                let cmd = build_command(puzzle, &Launcher::Bin, false, is_release);
+                assert_eq!(cmd.get_program(), "cargo");
+                assert_eq!(
+                    get_args(&cmd),
+                    [&["run", "--quiet", "--bin", "2025-08"], release, &["--"]].concat()
+                );
+
+                let cmd = build_command(puzzle, &Launcher::AllDays, true, is_release);
+                assert_eq!(cmd.get_program(), "cargo");
+                assert_eq!(
+                    get_args(&cmd),
+                    [
+                        &["run", "--quiet", "--bin", "all-days"],
+                        release,
+                        &["--", "day", "08", "--year", "2025", "--time"]
+                    ]
+                    .concat()
+                );
+            }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
+            let cmd = build_command(
+                puzzle,
+                &Launcher::CurrentExe(PathBuf::from("all-days")),
+                false,
+                true,
             );
-        }};
-    }
-
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
+            assert_eq!(cmd.get_program(), "all-days");
+            assert_eq!(get_args(&cmd), ["day", "08", "--year", "2025"]);
+        }
 
-        use crate::day;
+        fn result(part: u8, status: Status, answer: Option<&str>, nanos: u64) -> PartResult {
+            PartResult {
+                part,
+                status,
+                answer: answer.map(Into::into),
+                nanos,
+                samples: 100,
+                stats: None,
+                check: Check::Unknown,
+                message: None,
+                alloc: None,
+            }
+        }
 
         #[test]
         fn parses_execution_times() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    result(1, Status::Solved, Some("0"), 74),
+                    result(2, Status::Solved, Some("10"), 74_130_000),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.total_nanos(), 74_130_074);
+            assert_eq!(res.part_1.unwrap().nanos, 74);
+            assert_eq!(res.part_1.unwrap().samples, 100);
+            assert_eq!(res.part_2.unwrap().to_string(), "74.1ms");
+            assert_eq!(res.parse.is_none(), true);
+        }
+
+        #[test]
+        fn parses_parse_step() {
+            let res = parse_exec_time(
+                &[
+                    result(0, Status::Solved, None, 1_000),
+                    result(1, Status::Solved, Some("1"), 2_000),
+                    result(2, Status::Unsolved, None, 3_000),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.total_nanos(), 3_000);
+            assert_eq!(res.parse.unwrap().nanos, 1_000);
+            assert_eq!(res.part_1.unwrap().nanos, 2_000);
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn parses_with_patterns_in_answers() {
+            let res = parse_exec_time(
+                &[
+                    result(
+                        1,
+                        Status::Solved,
+                        Some("@ @ ( ) ms (2s @ 5 samples)"),
+                        2_000_000_000,
+                    ),
+                    result(2, Status::Solved, Some("#..#\n#..#"), 100_000_000),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos(), 2_100_000_000);
+            assert_eq!(res.part_1.unwrap().nanos, 2_000_000_000);
+            assert_eq!(res.part_2.unwrap().nanos, 100_000_000);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
+        fn parses_failed_parts() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    result(1, Status::Solved, Some("1"), 2_000),
+                    result(2, Status::Panic, None, 0),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap().nanos, 2_000);
+            assert_eq!(res.part_1_failure, None);
+            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.part_2_failure, Some(Status::Panic));
         }
 
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    result(1, Status::Unsolved, None, 10),
+                    result(2, Status::Unsolved, None, 10),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.total_nanos(), 0);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
@@ -1,56 +1,248 @@
 /// Encapsulates code that interacts with solution functions.
-use std::fmt::Display;
+use std::cell::{Cell, RefCell};
 use std::hint::black_box;
 use std::io::{Write, stdout};
-use std::process::Output;
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::alloc_stats::{self, AllocStats};
+use crate::template::answer::{Answer, ToAnswer};
+use crate::template::answers::{Answers, Check};
+use crate::template::guard::{self, Watchdog};
+use crate::template::protocol::{self, PartResult, Status};
+use crate::template::stats::{Stats, format_nanos};
+use crate::template::submissions::{self, Outcome, Submissions};
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Calendar, PuzzleId, client::AocClient};
+
+/// Environment variable with the wall-clock time in seconds a part may take before it is stopped, e.g. `AOC_TIMEOUT=10`.
+/// Only the first execution of a part counts towards the timeout, benchmark iterations do not.
+pub const TIMEOUT_ENV: &str = "AOC_TIMEOUT";
+
+/// Benches parts even though `--time` was not passed, used when solutions run in-process.
+static IS_TIMED: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn set_timed(is_timed: bool) {
+    IS_TIMED.store(is_timed, Ordering::Relaxed);
+}
+
+thread_local! {
+    /// Expected answers of the example that is running, see [`run_example`].
+    static EXAMPLE: RefCell<Option<Answers>> = const { RefCell::new(None) };
+    /// Whether the solution runs on an input other than the puzzle input, see [`run_other_input`].
+    static IS_OTHER_INPUT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `func` on an example: parts are checked against `expected` instead of the answer registry,
+/// solutions with parameters receive their example value, and no answer is accepted or submitted.
+pub(crate) fn run_example<R>(expected: Answers, func: impl FnOnce() -> R) -> R {
+    EXAMPLE.set(Some(expected));
+    let result = func();
+    EXAMPLE.set(None);
+    result
+}
+
+/// Runs `func` on an input other than the puzzle input, e.g. one passed with `--input`.
+/// Its answers are not compared with the answer registry, and no answer is accepted or submitted.
+pub(crate) fn run_other_input<R>(func: impl FnOnce() -> R) -> R {
+    IS_OTHER_INPUT.set(true);
+    let result = func();
+    IS_OTHER_INPUT.set(false);
+    result
+}
+
+/// Whether the solution runs on an example, see [`crate::template::example_cases`].
+pub fn is_example() -> bool {
+    EXAMPLE.with_borrow(Option::is_some)
+}
+
+pub fn run_part<I: Copy, T: ToAnswer>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) {
+    // the final day of an event only has one part.
+    if part > Calendar::new(puzzle.year).parts(puzzle.day) {
+        return;
+    }
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let outcome = guard::catch_panic(|| {
+        run_timed(&func, input, part, &part_str, |result| {
+            print_result(&result.as_ref().map(T::to_answer), &part_str, "", "");
+        })
+    });
+
+    let (result, duration, stats, alloc) = match outcome {
+        Ok(outcome) => outcome,
+        Err(message) => {
+            report_panic(part, &part_str, message);
+            return;
+        }
+    };
+
+    let result = result.as_ref().map(T::to_answer);
+    let answer = result.as_ref().map(Answer::normalized);
+    let expected = EXAMPLE.with_borrow(Clone::clone);
+    let is_puzzle_input = expected.is_none() && !IS_OTHER_INPUT.get();
+    let is_accepted = is_puzzle_input && accept_answer(puzzle, part, answer.as_deref());
+    let check = match expected {
+        Some(expected) => expected.check(part, answer.as_deref()),
+        None if is_puzzle_input => match Answers::read(puzzle) {
+            Ok(answers) => answers.check(part, answer.as_deref()),
+            Err(e) => {
+                eprintln!("Failed to read the answer registry: {e}");
+                Check::Unreadable
+            }
+        },
+        None => Check::Unknown,
+    };
+
+    let samples = stats.map_or(1, |s| s.samples);
+    print_result(
+        &result,
+        &part_str,
+        &format_duration(&duration, &stats),
+        &check.marker(),
+    );
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if let Some(stats) = &stats {
+        print_stats(stats);
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Some(alloc) = &alloc {
+        print_alloc(alloc);
+    }
+
+    if is_accepted {
+        println!("Accepted answer for part {part}.");
+    }
+
+    protocol::emit(&PartResult {
+        part,
+        status: if result.is_some() {
+            Status::Solved
+        } else {
+            Status::Unsolved
+        },
+        answer,
+        #[allow(clippy::cast_possible_truncation)]
+        nanos: duration.as_nanos() as u64,
+        samples,
+        stats,
+        check,
+        message: None,
+        alloc,
+    });
+
+    if let Some(result) = result
+        && is_puzzle_input
+    {
+        submit_result(&result, puzzle, part);
     }
 }
 
+/// Run the parse step of a solution and return its output, which is shared by both parts.
+/// The step is timed like a part and reported as part [`protocol::PARSE_PART`].
+/// If it panics, the panic is reported and passed on, since neither part can run without its output.
+pub fn run_parse<P>(func: impl Fn(&str) -> P, input: &str) -> P {
+    let outcome = guard::catch_panic(|| {
+        run_timed(&func, input, protocol::PARSE_PART, "Parse", |_| {
+            print!("Parse:");
+        })
+    });
+
+    let (parsed, duration, stats, alloc) = match outcome {
+        Ok(outcome) => outcome,
+        Err(message) => {
+            report_panic(protocol::PARSE_PART, "Parse", message.clone());
+            panic::resume_unwind(Box::new(message));
+        }
+    };
+
+    print!("\r");
+    println!("Parse:{}", format_duration(&duration, &stats));
+
+    if let Some(stats) = &stats {
+        print_stats(stats);
+    }
+
+    if let Some(alloc) = &alloc {
+        print_alloc(alloc);
+    }
+
+    protocol::emit(&PartResult {
+        part: protocol::PARSE_PART,
+        status: Status::Solved,
+        answer: None,
+        #[allow(clippy::cast_possible_truncation)]
+        nanos: duration.as_nanos() as u64,
+        samples: stats.map_or(1, |s| s.samples),
+        stats,
+        check: Check::Unknown,
+        message: None,
+        alloc,
+    });
+
+    parsed
+}
+
+fn report_panic(part: u8, label: &str, message: String) {
+    print!("\r");
+    println!("{label}: {ANSI_BOLD}panic{ANSI_RESET} {ANSI_ITALIC}({message}){ANSI_RESET}");
+
+    protocol::emit(&PartResult {
+        part,
+        status: Status::Panic,
+        answer: None,
+        nanos: 0,
+        samples: 0,
+        stats: None,
+        check: Check::Unknown,
+        message: Some(message),
+        alloc: None,
+    });
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    part: u8,
+    label: &str,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, Option<Stats>, Option<AllocStats>) {
+    let watchdog = Watchdog::start(part, label);
+
     let timer = Instant::now();
-    let result = {
+    let (result, alloc) = alloc_stats::measure(|| {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
         func(input)
-    };
+    });
     let base_time = timer.elapsed();
 
+    drop(watchdog);
+
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if IS_TIMED.load(Ordering::Relaxed) || env::args().any(|x| x == "--time") {
+        let stats = bench(func, input, &base_time);
+        (result, Duration::from_nanos(stats.mean), Some(stats), alloc)
     } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+        (result, base_time, None, alloc)
+    }
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Bench a solution part. Runs a number of warm-up iterations first, then collects one duration per sample.
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Stats {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -59,6 +251,10 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    for _ in 0..cmp::max(bench_iterations / 10, 1) {
+        black_box(func(black_box(input)));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -67,51 +263,61 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Stats::from_samples(&timers).expect("bench collects at least one sample")
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_duration(duration: &Duration, stats: &Option<Stats>) -> String {
+    match stats {
+        Some(stats) => format!(
+            " ({duration:.1?} ± {} @ {} samples)",
+            format_nanos(stats.stddev),
+            stats.samples
+        ),
+        None => format!(" ({duration:.1?})"),
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
-    }
+fn print_stats(stats: &Stats) {
+    println!(
+        "  {ANSI_ITALIC}min {} · median {} · p95 {} · 95% CI [{}, {}] · {} outliers{ANSI_RESET}",
+        format_nanos(stats.min),
+        format_nanos(stats.median),
+        format_nanos(stats.p95),
+        format_nanos(stats.ci_low),
+        format_nanos(stats.ci_high),
+        stats.outliers
+    );
+}
+
+fn print_alloc(alloc: &AllocStats) {
+    println!("  {ANSI_ITALIC}{}{ANSI_RESET}", alloc.summary());
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: &Option<Answer>, part: &str, duration_str: &str, marker: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
+        Some(result @ Answer::Glyphs(_)) => {
+            let letters = result
+                .letters()
+                .map(|letters| format!("{ANSI_BOLD}{letters}{ANSI_RESET} "))
+                .unwrap_or_default();
+            let str = format!("{part}: {letters}▼ {duration_str}{marker}");
+            if is_intermediate_result {
+                print!("{str}");
+            } else {
+                print!("\r");
+                println!("{str}");
+                println!("{result}");
+            }
+        }
         Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
+            let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}{marker}");
+            if is_intermediate_result {
+                print!("{str}");
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
+                print!("\r");
+                println!("{str}");
             }
         }
         None => {
@@ -119,24 +325,53 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 print!("{part}: ✖");
             } else {
                 print!("\r");
-                println!("{part}: ✖             ");
+                println!("{part}: ✖{marker}             ");
             }
         }
     }
 }
 
+/// Store an answer in the answer registry if `--accept` was passed.
+/// Returns whether the answer was stored.
+fn accept_answer(puzzle: PuzzleId, part: u8, answer: Option<&str>) -> bool {
+    let Some(answer) = answer else {
+        return false;
+    };
+
+    if !env::args().any(|x| x == "--accept") {
+        return false;
+    }
+
+    // NOTE: storing into an unreadable registry would drop the answer of the other part.
+    let mut answers = match Answers::read(puzzle) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Failed to store accepted answer: {e}");
+            return false;
+        }
+    };
+    answers.set(part, answer.to_string());
+
+    match answers.store(puzzle) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("Failed to store accepted answer: {e}");
+            false
+        }
+    }
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  2. a session cookie is configured.
+///  3. the answer is not known to be wrong from previous submissions.
+///
+/// The server response is recorded in the submission log. Correct answers are also added to the answer registry.
+fn submit_result(result: &Answer, puzzle: PuzzleId, part: u8) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -152,16 +387,54 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
+    let answer = result.normalized();
+    let mut submissions = match Submissions::read(puzzle) {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!("Refusing to submit part {part}: failed to read previous submissions, {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(refusal) = submissions.check(part, &answer, submissions::now()) {
+        eprintln!("Refusing to submit part {part}: {refusal}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to set up client: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("Submitting result...");
+    let response = match client.submit(puzzle, part, &answer) {
+        Ok(response) => response,
+        Err(e) => {
+            eprintln!("Failed to submit result: {e}");
+            process::exit(1);
+        }
+    };
+    println!("{response}");
+
+    let outcome = Outcome::parse(&response);
+    submissions.record(part, &answer, outcome);
+    if let Err(e) = submissions.store(puzzle) {
+        eprintln!("Failed to store submission: {e}");
+    }
+
+    if outcome == Outcome::Correct {
+        let stored = Answers::read(puzzle).and_then(|mut answers| {
+            answers.set(part, answer);
+            answers.store(puzzle).map_err(|e| e.to_string())
+        });
+        if let Err(e) = stored {
+            eprintln!("Failed to store accepted answer: {e}");
+        }
+    }
 }
@@ -0,0 +1,203 @@
+/// Summary statistics over benchmark samples.
+///
+/// Outliers are classified with Tukey's fences (1.5 × IQR) like criterion does, and excluded from the mean,
+/// standard deviation and confidence interval so a single slow iteration does not skew the result.
+/// Order statistics (min, median, p95) are computed over all samples.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// z-score for a two-sided 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Mean of all samples within the fences, in nanoseconds.
+    pub mean: u64,
+    pub min: u64,
+    pub median: u64,
+    pub p95: u64,
+    pub stddev: u64,
+    /// Lower bound of the 95% confidence interval of the mean.
+    pub ci_low: u64,
+    /// Upper bound of the 95% confidence interval of the mean.
+    pub ci_high: u64,
+    pub samples: u64,
+    /// Number of samples outside the fences.
+    pub outliers: u64,
+}
+
+impl Stats {
+    /// Computes statistics over a set of samples, returns [`None`] if `samples` is empty.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<u64> = samples.iter().map(|d| d.as_nanos() as u64).collect();
+        sorted.sort_unstable();
+
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let iqr = q3 - q1;
+        let (fence_low, fence_high) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        let kept: Vec<f64> = sorted
+            .iter()
+            .map(|&x| x as f64)
+            .filter(|&x| x >= fence_low && x <= fence_high)
+            .collect();
+
+        let n = kept.len() as f64;
+        let mean = kept.iter().sum::<f64>() / n;
+        let stddev = if kept.len() > 1 {
+            (kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+        let margin = Z_95 * stddev / n.sqrt();
+
+        Some(Self {
+            mean: mean.round() as u64,
+            min: sorted[0],
+            median: percentile(&sorted, 0.5).round() as u64,
+            p95: percentile(&sorted, 0.95).round() as u64,
+            stddev: stddev.round() as u64,
+            ci_low: (mean - margin).max(0.0).round() as u64,
+            ci_high: (mean + margin).round() as u64,
+            samples: sorted.len() as u64,
+            outliers: (sorted.len() - kept.len()) as u64,
+        })
+    }
+}
+
+/// Linear interpolation between closest ranks. Expects `sorted` to be non-empty and sorted.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[u64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    let weight = rank - rank.floor();
+    sorted[lower] as f64 * (1.0 - weight) + sorted[upper] as f64 * weight
+}
+
+/// Formats a nanosecond value the same way [`Duration`]'s debug output does, e.g. `74.1ns` or `1.2ms`.
+pub fn format_nanos(nanos: u64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Stats) -> Self {
+        let map: HashMap<String, JsonValue> = [
+            ("mean", value.mean),
+            ("min", value.min),
+            ("median", value.median),
+            ("p95", value.p95),
+            ("stddev", value.stddev),
+            ("ci_low", value.ci_low),
+            ("ci_high", value.ci_high),
+            ("samples", value.samples),
+            ("outliers", value.outliers),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), JsonValue::Number(v as f64)))
+        .collect();
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as u64)
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(Self {
+            mean: number("mean")?,
+            min: number("min")?,
+            median: number("median")?,
+            p95: number("p95")?,
+            stddev: number("stddev")?,
+            ci_low: number("ci_low")?,
+            ci_high: number("ci_high")?,
+            samples: number("samples")?,
+            outliers: number("outliers")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&x| Duration::from_nanos(x)).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42])).unwrap();
+        assert_eq!(stats.mean, 42);
+        assert_eq!(stats.median, 42);
+        assert_eq!(stats.stddev, 0);
+        assert_eq!(stats.ci_low, 42);
+        assert_eq!(stats.ci_high, 42);
+    }
+
+    #[test]
+    fn computes_order_statistics() {
+        let samples: Vec<u64> = (1..=100).collect();
+        let stats = Stats::from_samples(&nanos(&samples)).unwrap();
+        assert_eq!(stats.min, 1);
+        assert_eq!(stats.median, 51);
+        assert_eq!(stats.p95, 95);
+        assert_eq!(stats.samples, 100);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn rejects_outliers_from_mean() {
+        let stats = Stats::from_samples(&nanos(&[10, 11, 9, 10, 10, 11, 9, 10, 5000])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.mean, 10);
+        assert_eq!(stats.samples, 9);
+        assert!(stats.ci_low <= 10 && stats.ci_high >= 10);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = Stats::from_samples(&nanos(&[10, 20, 30, 40])).unwrap();
+        let json = JsonValue::from(&stats);
+        assert_eq!(Stats::try_from(&json).unwrap(), stats);
+    }
+}
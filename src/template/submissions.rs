@@ -0,0 +1,521 @@
+/// Log of submitted answers and the server's response to them.
+/// Submissions for a day are stored in `data/{year}/submissions/{day}.json` and used to refuse
+/// answers locally that are known to be wrong, which avoids lockout penalties.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs, io,
+    path::PathBuf,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::PuzzleId;
+
+/// Server response to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong answer without a hint.
+    Incorrect,
+    /// The answer was submitted too recently after a previous one and was not checked.
+    Wait {
+        seconds: u64,
+    },
+    /// The part has already been solved.
+    AlreadySolved,
+    /// The response could not be recognized.
+    Unknown,
+}
+
+impl Outcome {
+    /// Recognizes the outcome from the text of a submission response.
+    pub fn parse(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Outcome::Correct
+        } else if response.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if response.contains("your answer is too low") {
+            Outcome::TooLow
+        } else if response.contains("That's not the right answer") {
+            Outcome::Incorrect
+        } else if response.contains("You gave an answer too recently") {
+            Outcome::Wait {
+                seconds: parse_wait(response).unwrap_or(60),
+            }
+        } else if response.contains("Did you already complete it?") {
+            Outcome::AlreadySolved
+        } else {
+            Outcome::Unknown
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::Incorrect => "incorrect",
+            Outcome::Wait { .. } => "wait",
+            Outcome::AlreadySolved => "already_solved",
+            Outcome::Unknown => "unknown",
+        }
+    }
+
+    fn is_rejected(self) -> bool {
+        matches!(
+            self,
+            Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect
+        )
+    }
+}
+
+/// Parses the remaining wait time from e.g. "You have 1m 23s left to wait.".
+fn parse_wait(response: &str) -> Option<u64> {
+    let (before, _) = response.split_once(" left to wait")?;
+    let (_, duration) = before.rsplit_once("You have ")?;
+
+    duration.split_whitespace().try_fold(0, |acc, part| {
+        let (value, multiplier) = if let Some(x) = part.strip_suffix('h') {
+            (x, 3600)
+        } else if let Some(x) = part.strip_suffix('m') {
+            (x, 60)
+        } else {
+            (part.strip_suffix('s')?, 1)
+        };
+        Some(acc + value.parse::<u64>().ok()? * multiplier)
+    })
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// Reason for refusing to submit an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved { answer: String },
+    AlreadyRejected,
+    TooHigh { bound: String },
+    TooLow { bound: String },
+    Wait { seconds: u64 },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            Refusal::AlreadyRejected => write!(f, "this answer was already rejected."),
+            Refusal::TooHigh { bound } => {
+                write!(f, "this answer is too high, {bound} was already too high.")
+            }
+            Refusal::TooLow { bound } => {
+                write!(f, "this answer is too low, {bound} was already too low.")
+            }
+            Refusal::Wait { seconds } => {
+                write!(f, "please wait {seconds}s before submitting again.")
+            }
+        }
+    }
+}
+
+/// Submissions for a single day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Submissions {
+    pub part_1: Vec<Submission>,
+    pub part_2: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Reads the submissions for `puzzle`. If not present, returns empty submissions.
+    /// A file that can't be read or parsed is an error, since submitting without its history could repeat wrong answers.
+    pub fn read(puzzle: PuzzleId) -> Result<Self, String> {
+        let path = get_submissions_path(puzzle);
+        Submissions::from_file(fs::read_to_string(&path))
+            .map_err(|e| format!("could not read \"{}\": {e}", path.display()))
+    }
+
+    fn from_file(contents: io::Result<String>) -> Result<Self, String> {
+        match contents {
+            Ok(contents) => Submissions::try_from(contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Writes the submissions for `puzzle`, creating `data/{year}/submissions` if needed.
+    pub fn store(&self, puzzle: PuzzleId) -> Result<(), io::Error> {
+        let path = get_submissions_path(puzzle);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    pub fn get(&self, part: u8) -> &[Submission] {
+        match part {
+            1 => &self.part_1,
+            2 => &self.part_2,
+            _ => &[],
+        }
+    }
+
+    /// Appends a submission for `part`, timestamped with the current time.
+    pub fn record(&mut self, part: u8, answer: &str, outcome: Outcome) {
+        let submission = Submission {
+            answer: answer.trim().to_string(),
+            outcome,
+            timestamp: now(),
+        };
+
+        match part {
+            1 => self.part_1.push(submission),
+            2 => self.part_2.push(submission),
+            _ => {}
+        }
+    }
+
+    /// Checks whether `answer` may be submitted for `part` at `timestamp`.
+    pub fn check(&self, part: u8, answer: &str, timestamp: u64) -> Result<(), Refusal> {
+        let answer = answer.trim();
+        let submissions = self.get(part);
+
+        if let Some(correct) = submissions.iter().find(|s| s.outcome == Outcome::Correct) {
+            return Err(Refusal::AlreadySolved {
+                answer: correct.answer.clone(),
+            });
+        }
+
+        if let Some(seconds) = submissions.iter().find_map(|s| match s.outcome {
+            Outcome::Wait { seconds } if s.timestamp + seconds > timestamp => {
+                Some(s.timestamp + seconds - timestamp)
+            }
+            _ => None,
+        }) {
+            return Err(Refusal::Wait { seconds });
+        }
+
+        if submissions
+            .iter()
+            .any(|s| s.outcome.is_rejected() && s.answer == answer)
+        {
+            return Err(Refusal::AlreadyRejected);
+        }
+
+        // bounds only apply to numeric answers.
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |outcome: Outcome| {
+            submissions
+                .iter()
+                .filter(move |s| s.outcome == outcome)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(bound) = bound(Outcome::TooHigh).min()
+            && value >= bound
+        {
+            return Err(Refusal::TooHigh {
+                bound: bound.to_string(),
+            });
+        }
+
+        if let Some(bound) = bound(Outcome::TooLow).max()
+            && value <= bound
+        {
+            return Err(Refusal::TooLow {
+                bound: bound.to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Current time in seconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn get_submissions_path(puzzle: PuzzleId) -> PathBuf {
+    puzzle.data_path("submissions", "json")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submissions> for JsonValue {
+    fn from(value: &Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (key, submissions) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                JsonValue::Array(submissions.iter().map(JsonValue::from).collect()),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let submissions = |key: &str| match json.get(key) {
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or(format!("expected submissions.{key} to be an array."))?
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>(),
+            None => Ok(vec![]),
+        };
+
+        Ok(Submissions {
+            part_1: submissions("part_1")?,
+            part_2: submissions("part_2")?,
+        })
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(value.outcome.as_str().into()),
+        );
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        if let Outcome::Wait { seconds } = value.outcome {
+            map.insert("wait_seconds".into(), JsonValue::Number(seconds as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected submission to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("expected submission.{key} to be a string."))
+        };
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or(format!("expected submission.{key} to be a number."))
+        };
+
+        let outcome = match string("outcome")?.as_str() {
+            "correct" => Outcome::Correct,
+            "too_high" => Outcome::TooHigh,
+            "too_low" => Outcome::TooLow,
+            "incorrect" => Outcome::Incorrect,
+            "wait" => Outcome::Wait {
+                seconds: number("wait_seconds")?,
+            },
+            "already_solved" => Outcome::AlreadySolved,
+            _ => Outcome::Unknown,
+        };
+
+        Ok(Submission {
+            answer: string("answer")?,
+            outcome,
+            timestamp: number("timestamp")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io;
+
+    use super::{Outcome, Refusal, Submission, Submissions};
+
+    fn submission(answer: &str, outcome: Outcome, timestamp: u64) -> Submission {
+        Submission {
+            answer: answer.into(),
+            outcome,
+            timestamp,
+        }
+    }
+
+    mod parse {
+        use super::super::Outcome;
+
+        #[test]
+        fn parses_correct_answers() {
+            let response = "That's the right answer! You are one gold star closer to decorating the North Pole. [Continue to Part Two]";
+            assert_eq!(Outcome::parse(response), Outcome::Correct);
+        }
+
+        #[test]
+        fn parses_bounds() {
+            let response = "That's not the right answer; your answer is too high. If you're stuck, make sure you're using the full input data; please wait one minute before trying again.";
+            assert_eq!(Outcome::parse(response), Outcome::TooHigh);
+            let response = "That's not the right answer; your answer is too low. Curiously, it's the right answer for someone else.";
+            assert_eq!(Outcome::parse(response), Outcome::TooLow);
+        }
+
+        #[test]
+        fn parses_wrong_answers() {
+            let response = "That's not the right answer. If you're stuck, make sure you're using the full input data.";
+            assert_eq!(Outcome::parse(response), Outcome::Incorrect);
+        }
+
+        #[test]
+        fn parses_wait_times() {
+            let response = "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait. [Return to Day 1]";
+            assert_eq!(Outcome::parse(response), Outcome::Wait { seconds: 83 });
+            let response = "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 9s left to wait.";
+            assert_eq!(Outcome::parse(response), Outcome::Wait { seconds: 9 });
+        }
+
+        #[test]
+        fn parses_solved_parts() {
+            let response = "You don't seem to be solving the right level.  Did you already complete it? [Return to Day 1]";
+            assert_eq!(Outcome::parse(response), Outcome::AlreadySolved);
+        }
+
+        #[test]
+        fn handles_unknown_responses() {
+            assert_eq!(Outcome::parse("502 Bad Gateway"), Outcome::Unknown);
+        }
+    }
+
+    #[test]
+    fn allows_new_answers() {
+        let submissions = Submissions::default();
+        assert_eq!(submissions.check(1, "42", 0), Ok(()));
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let submissions = Submissions {
+            part_1: vec![submission("abc", Outcome::Incorrect, 0)],
+            part_2: vec![],
+        };
+        assert_eq!(
+            submissions.check(1, "abc", 1000),
+            Err(Refusal::AlreadyRejected)
+        );
+        assert_eq!(submissions.check(2, "abc", 1000), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let submissions = Submissions {
+            part_1: vec![
+                submission("100", Outcome::TooHigh, 0),
+                submission("80", Outcome::TooHigh, 0),
+                submission("10", Outcome::TooLow, 0),
+            ],
+            part_2: vec![],
+        };
+        assert_eq!(
+            submissions.check(1, "90", 1000),
+            Err(Refusal::TooHigh { bound: "80".into() })
+        );
+        assert_eq!(
+            submissions.check(1, "5", 1000),
+            Err(Refusal::TooLow { bound: "10".into() })
+        );
+        assert_eq!(submissions.check(1, "50", 1000), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_while_waiting() {
+        let submissions = Submissions {
+            part_1: vec![submission("1", Outcome::Wait { seconds: 60 }, 1000)],
+            part_2: vec![],
+        };
+        assert_eq!(
+            submissions.check(1, "2", 1030),
+            Err(Refusal::Wait { seconds: 30 })
+        );
+        assert_eq!(submissions.check(1, "2", 1060), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let submissions = Submissions {
+            part_1: vec![submission("42", Outcome::Correct, 0)],
+            part_2: vec![],
+        };
+        assert_eq!(
+            submissions.check(1, "43", 0),
+            Err(Refusal::AlreadySolved {
+                answer: "42".into()
+            })
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let submissions = Submissions {
+            part_1: vec![
+                submission("100", Outcome::TooHigh, 1_733_000_000),
+                submission("50", Outcome::Wait { seconds: 42 }, 1_733_000_060),
+            ],
+            part_2: vec![submission("x", Outcome::Correct, 1_733_000_120)],
+        };
+        let json = tinyjson::JsonValue::from(&submissions).stringify().unwrap();
+        assert_eq!(Submissions::try_from(json).unwrap(), submissions);
+    }
+
+    #[test]
+    fn errors_for_unreadable_files() {
+        let missing = io::Error::from(io::ErrorKind::NotFound);
+        assert_eq!(
+            Submissions::from_file(Err(missing)),
+            Ok(Submissions::default())
+        );
+
+        let denied = io::Error::from(io::ErrorKind::PermissionDenied);
+        assert!(Submissions::from_file(Err(denied)).is_err());
+        assert!(Submissions::from_file(Ok("{\"part_1\": [".into())).is_err());
+    }
+}
@@ -1,17 +1,68 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, path::Path, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    Day, Year,
+    alloc_stats::AllocStats,
+    protocol::Status,
+    stats::{Stats, format_nanos},
+};
+
+static TIMINGS_FILE_STEM: &str = "timings";
+
+/// File name of data kept per variant of the benchmarks, e.g. `timings.json` for the default
+/// variant and `timings-debug.json` for `cargo time --variant debug`.
+pub fn variant_file_name(stem: &str, variant: Option<&str>, extension: &str) -> String {
+    match variant {
+        Some(variant) => format!("{stem}-{variant}.{extension}"),
+        None => format!("{stem}.{extension}"),
+    }
+}
+
+/// Mean duration of a benched step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Measurement {
+    pub nanos: u64,
+    /// Number of samples the mean was taken over. `0` for timings migrated from the string format
+    /// without stats, see [`parse_legacy_duration`].
+    pub samples: u64,
+}
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+impl std::fmt::Display for Measurement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", format_nanos(self.nanos))
+    }
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
-    pub total_nanos: f64,
+    pub parse: Option<Measurement>,
+    pub parse_stats: Option<Stats>,
+    pub part_1: Option<Measurement>,
+    pub part_2: Option<Measurement>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    /// Set when a step did not produce a timing because it panicked or timed out.
+    pub parse_failure: Option<Status>,
+    pub part_1_failure: Option<Status>,
+    pub part_2_failure: Option<Status>,
+    /// Heap usage of each step, only recorded with the `alloc-stats` feature.
+    pub parse_alloc: Option<AllocStats>,
+    pub part_1_alloc: Option<AllocStats>,
+    pub part_2_alloc: Option<AllocStats>,
+}
+
+impl Timing {
+    /// Sum of the durations of all benched steps.
+    pub fn total_nanos(&self) -> u64 {
+        [self.parse, self.part_1, self.part_2]
+            .iter()
+            .flatten()
+            .map(|m| m.nanos)
+            .sum()
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,19 +73,29 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings to the JSON file of `year`, e.g. `data/2025/timings.json`.
+    pub fn store_file(&self, year: Year, variant: Option<&str>) -> Result<(), Error> {
+        let dir = year.data_dir();
+        fs::create_dir_all(&dir)?;
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(Path::new(&dir).join(variant_file_name(
+            TIMINGS_FILE_STEM,
+            variant,
+            "json",
+        )))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Rehydrate timings from the JSON file of `year`. If not present, returns empty timings.
+    pub fn read_from_file(year: Year, variant: Option<&str>) -> Self {
+        fs::read_to_string(Path::new(&year.data_dir()).join(variant_file_name(
+            TIMINGS_FILE_STEM,
+            variant,
+            "json",
+        )))
+        .map_err(|x| x.to_string())
+        .and_then(Timings::try_from)
+        .unwrap_or_default()
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
@@ -51,19 +112,21 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
     /// Sum up total duration of timings as millis.
+    #[allow(clippy::cast_precision_loss)]
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        self.data.iter().map(Timing::total_nanos).sum::<u64>() as f64 / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
+    /// Whether every part of `day` has been benched. `parts` is the number of parts the puzzle has.
+    pub fn is_day_complete(&self, day: Day, parts: u8) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.day == day && t.part_1.is_some() && (parts < 2 || t.part_2.is_some()))
     }
 }
 
@@ -112,27 +175,57 @@ impl From<&Timing> for JsonValue {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
-        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::from(parse));
+        }
 
         map.insert(
             "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            value
+                .part_1
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
         );
 
         map.insert(
             "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            value
+                .part_2
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
         );
 
+        for (key, stats) in [
+            ("parse_stats", &value.parse_stats),
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            if let Some(stats) = stats {
+                map.insert(key.into(), JsonValue::from(stats));
+            }
+        }
+
+        for (key, alloc) in [
+            ("parse_alloc", &value.parse_alloc),
+            ("part_1_alloc", &value.part_1_alloc),
+            ("part_2_alloc", &value.part_2_alloc),
+        ] {
+            if let Some(alloc) = alloc {
+                map.insert(key.into(), JsonValue::from(alloc));
+            }
+        }
+
+        for (key, failure) in [
+            ("parse_failure", value.parse_failure),
+            ("part_1_failure", value.part_1_failure),
+            ("part_2_failure", value.part_2_failure),
+        ] {
+            if let Some(failure) = failure {
+                map.insert(key.into(), JsonValue::String(failure.as_str().into()));
+            }
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -151,76 +244,271 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        // NOTE: stats are optional, timings stored by older versions do not have them.
+        let stats = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => Stats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
+        let parse_stats = stats("parse_stats")?;
+        let part_1_stats = stats("part_1_stats")?;
+        let part_2_stats = stats("part_2_stats")?;
+
+        // NOTE: the parse step is optional, solutions without a parse hook do not have it.
+        let parse = match json.get("parse") {
+            Some(v) => measurement(v, parse_stats)
+                .ok_or("Expected timing.parse to be null or a measurement.")?,
+            None => None,
+        };
+
         let part_1 = json
             .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+            .and_then(|v| measurement(v, part_1_stats))
+            .ok_or("Expected timing.part_1 to be null or a measurement.")?;
 
         let part_2 = json
             .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+            .and_then(|v| measurement(v, part_2_stats))
+            .ok_or("Expected timing.part_2 to be null or a measurement.")?;
 
-        let total_nanos = json
-            .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
-            .ok_or("Expected timing.total_nanos to be a number.")?;
+        let alloc = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => AllocStats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
+        // NOTE: failures are only stored for steps that panicked or timed out.
+        let failure = |key: &str| match json.get(key).and_then(|v| v.get::<String>()) {
+            Some(v) => v.parse().map(Some),
+            None => Ok(None),
+        };
 
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
+            parse,
+            parse_stats,
+            part_1,
+            part_2,
+            part_1_stats,
+            part_2_stats,
+            parse_failure: failure("parse_failure")?,
+            part_1_failure: failure("part_1_failure")?,
+            part_2_failure: failure("part_2_failure")?,
+            parse_alloc: alloc("parse_alloc")?,
+            part_1_alloc: alloc("part_1_alloc")?,
+            part_2_alloc: alloc("part_2_alloc")?,
         })
     }
 }
 
+impl From<&Measurement> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Measurement) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("nanos".into(), JsonValue::Number(value.nanos as f64));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        JsonValue::Object(map)
+    }
+}
+
+/// Reads a measurement of a step, `Some(None)` if the step was not benched.
+///
+/// Timings stored by older versions hold a formatted string like `"74.1ms"` instead. These are migrated
+/// by taking the exact mean from `stats` if present, and by parsing the string otherwise.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn measurement(value: &JsonValue, stats: Option<Stats>) -> Option<Option<Measurement>> {
+    if value.is_null() {
+        return Some(None);
+    }
+
+    if let Some(legacy) = value.get::<String>() {
+        let measurement = match stats {
+            Some(stats) => Measurement {
+                nanos: stats.mean,
+                samples: stats.samples,
+            },
+            None => Measurement {
+                nanos: parse_legacy_duration(legacy)?,
+                samples: 0,
+            },
+        };
+        return Some(Some(measurement));
+    }
+
+    let map = value.get::<HashMap<String, JsonValue>>()?;
+    let number = |key: &str| map.get(key).and_then(|v| v.get::<f64>()).map(|v| *v as u64);
+
+    Some(Some(Measurement {
+        nanos: number("nanos")?,
+        samples: number("samples")?,
+    }))
+}
+
+/// Parses a duration in the debug format of [`std::time::Duration`], e.g. `74.1ms`, into nanoseconds.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_legacy_duration(s: &str) -> Option<u64> {
+    let s = s.trim();
+    let (value, factor) = [
+        ("ns", 1.0),
+        ("µs", 1e3),
+        ("us", 1e3),
+        ("ms", 1e6),
+        ("s", 1e9),
+    ]
+    .into_iter()
+    .find_map(|(unit, factor)| s.strip_suffix(unit).map(|value| (value, factor)))?;
+
+    let value: f64 = value.trim().parse().ok()?;
+    (value >= 0.0).then(|| (value * factor).round() as u64)
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{Measurement, Timing, Timings};
+
+    fn millis(millis: u64) -> Measurement {
+        Measurement {
+            nanos: millis * 1_000_000,
+            samples: 100,
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    parse: None,
+                    parse_stats: None,
+                    part_1: Some(millis(10)),
+                    part_2: Some(millis(20)),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_failure: None,
+                    part_1_failure: None,
+                    part_2_failure: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    parse: None,
+                    parse_stats: None,
+                    part_1: Some(millis(30)),
+                    part_2: Some(millis(40)),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_failure: None,
+                    part_1_failure: None,
+                    part_2_failure: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    parse: None,
+                    parse_stats: None,
+                    part_1: Some(millis(40)),
                     part_2: None,
-                    total_nanos: 4e+10,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_failure: None,
+                    part_1_failure: None,
+                    part_2_failure: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::protocol::Status,
+            template::timings::{Measurement, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "nanos": 74130, "samples": 1000 }, "part_2": null }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(
+                timing.part_1,
+                Some(Measurement {
+                    nanos: 74_130,
+                    samples: 1000
+                })
+            );
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.total_nanos(), 74_130);
+            assert_eq!(timing.part_1_stats, None);
+            assert_eq!(timing.parse, None);
+        }
+
+        #[test]
+        fn handles_json_parse_timings() {
+            let json = r#"{ "data": [{ "day": "01", "parse": { "nanos": 2000000, "samples": 10 }, "part_1": { "nanos": 1000000, "samples": 10 }, "part_2": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse.unwrap().nanos, 2_000_000);
+            assert_eq!(timing.parse_stats, None);
+            assert_eq!(timing.total_nanos(), 3_000_000);
+        }
+
+        #[test]
+        fn handles_json_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "nanos": 1000000, "samples": 1000 }, "part_2": null, "part_1_stats": { "mean": 1000000, "min": 900000, "median": 1000000, "p95": 1100000, "stddev": 50000, "ci_low": 990000, "ci_high": 1010000, "samples": 1000, "outliers": 3 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.unwrap();
+            assert_eq!(stats.median, 1_000_000);
+            assert_eq!(stats.outliers, 3);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
+        #[test]
+        fn migrates_legacy_timings() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "2.5µs", "part_1": "74.1ms", "part_2": "1s", "total_nanos": 1074102500 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse.unwrap().nanos, 2_500);
+            assert_eq!(timing.part_1.unwrap().nanos, 74_100_000);
+            assert_eq!(timing.part_2.unwrap().nanos, 1_000_000_000);
+            assert_eq!(timing.part_2.unwrap().samples, 0);
+            assert_eq!(timing.total_nanos(), 1_074_102_500);
+        }
+
+        #[test]
+        fn migrates_legacy_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "74.1ns", "part_2": null, "total_nanos": 74.13, "part_1_stats": { "mean": 74, "min": 70, "median": 74, "p95": 80, "stddev": 2, "ci_low": 73, "ci_high": 75, "samples": 10000, "outliers": 0 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.part_1,
+                Some(Measurement {
+                    nanos: 74,
+                    samples: 10000
+                })
+            );
+        }
+
+        #[test]
+        fn handles_json_failures() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "nanos": 1000000, "samples": 10 }, "part_2": null, "part_2_failure": "panic" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_failure, None);
+            assert_eq!(timing.part_2_failure, Some(Status::Panic));
         }
 
         #[test]
@@ -240,16 +528,27 @@ mod tests {
         #[test]
         #[should_panic]
         fn panics_for_malformed_timings() {
-            let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_1": "10 parsecs", "part_2": null }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
     }
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::Timings;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
+        #[test]
+        fn roundtrips_timings() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(parsed.data[2].part_2, None);
+            assert_eq!(parsed.total_millis(), 140.0);
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -274,18 +573,29 @@ mod tests {
             template::timings::{Timing, Timings},
         };
 
+        use super::millis;
+
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
+                    parse: None,
+                    parse_stats: None,
+                    part_1: Some(millis(1)),
+                    part_2: Some(millis(2)),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_failure: None,
+                    part_1_failure: None,
+                    part_2_failure: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1), 2), true);
         }
 
         #[test]
@@ -293,13 +603,24 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    parse: None,
+                    parse_stats: None,
+                    part_1: Some(millis(1)),
                     part_2: None,
-                    total_nanos: 1_000_000_000_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_failure: None,
+                    part_1_failure: None,
+                    part_2_failure: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1), 2), false);
+            // the final day of an event only has one part.
+            assert_eq!(timings.is_day_complete(day!(1), 1), true);
         }
 
         #[test]
@@ -307,13 +628,22 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
+                    parse_stats: None,
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0.0,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_failure: None,
+                    part_1_failure: None,
+                    part_2_failure: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1), 2), false);
         }
     }
 
@@ -331,9 +661,18 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
+                    parse_stats: None,
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_failure: None,
+                    part_1_failure: None,
+                    part_2_failure: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -351,9 +690,18 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
+                    parse_stats: None,
                     part_1: None,
                     part_2: None,
-                    total_nanos: 0_f64,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_failure: None,
+                    part_1_failure: None,
+                    part_2_failure: None,
+                    parse_alloc: None,
+                    part_1_alloc: None,
+                    part_2_alloc: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,7 +709,7 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].total_nanos(), 0);
             assert_eq!(merged.data[2].day, day!(4));
         }
 
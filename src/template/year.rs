@@ -0,0 +1,148 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The first year Advent of Code took place.
+const FIRST_YEAR: u16 = 2015;
+
+/// A valid year of Advent of Code (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2025).unwrap();
+/// assert_eq!(year.to_string(), "2025")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub const fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR || year > 9999 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Reads the default year from the `AOC_YEAR` environment variable.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// Reads the year from the module path of a solution, e.g. `2025_01` for the binary `2025-01`
+    /// or `all_days::_2025_01` when the solution is compiled into the in-process runner.
+    pub const fn from_module_path(path: &str) -> Option<Self> {
+        let bytes = path.as_bytes();
+
+        // skip to the last path segment and an optional leading underscore.
+        let mut start = 0;
+        let mut i = 0;
+        while i + 1 < bytes.len() {
+            if bytes[i] == b':' && bytes[i + 1] == b':' {
+                start = i + 2;
+            }
+            i += 1;
+        }
+        if start < bytes.len() && bytes[start] == b'_' {
+            start += 1;
+        }
+
+        if bytes.len() < start + 5 || (bytes[start + 4] != b'_' && bytes[start + 4] != b'-') {
+            return None;
+        }
+
+        let mut year: u16 = 0;
+        let mut i = start;
+        while i < start + 4 {
+            if !bytes[i].is_ascii_digit() {
+                return None;
+            }
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        Self::new(year)
+    }
+
+    /// Directory that holds the data of this year, e.g. `data/2025`.
+    pub fn data_dir(self) -> String {
+        format!("data/{self}")
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {
+        const { $crate::template::Year::new($year).expect("invalid year, expecting 2015 or later") }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn validates_years() {
+        assert_eq!(Year::new(2014), None);
+        assert_eq!(Year::new(2015), Some(Year(2015)));
+        assert!("2025".parse::<Year>().is_ok());
+        assert!("25".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn reads_years_from_module_paths() {
+        assert_eq!(Year::from_module_path("2025_01"), Some(Year(2025)));
+        assert_eq!(Year::from_module_path("2024-09-viz"), Some(Year(2024)));
+        assert_eq!(
+            Year::from_module_path("all_days::_2023_05"),
+            Some(Year(2023))
+        );
+        assert_eq!(Year::from_module_path("01"), None);
+        assert_eq!(Year::from_module_path("abcd_01"), None);
+        assert_eq!(Year::from_module_path("advent_of_code"), None);
+    }
+}